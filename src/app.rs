@@ -1,20 +1,38 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::io::Read;
 use std::ops::Range;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
 use std::vec;
 
+use ratatui::layout::Rect;
 use ratatui::widgets::TableState;
 use rfd::FileDialog;
 
+use crate::event::Event;
+use crate::job::{Job, JobContext};
+use crate::matcher::{MatchMode, Matcher};
 use crate::parser;
+use crate::parser::LevelRule;
+use crate::parser::LogEntry;
 use crate::parser::LogEntryIndices;
+use crate::parser::ParserSettings;
+use crate::parser::Severity;
+use crate::query::{like_query, parse_date, parse_duration, Query};
+use crate::timezone::Zone;
 use crate::thirdparty::input::Input;
 use log::info;
 
+use crate::tab::EntryStore;
+use crate::tab::RowKind;
 use crate::tab::Tab;
 use crate::tab::TabType;
 use crate::tab::TableItems;
+use crate::tab::ViewRow;
 
 use serde_json::{json, Value};
 use std::io::prelude::Write;
@@ -27,11 +45,214 @@ const CONFIG_FILE_NAME: &str = "log-viewer-rs-config.json";
 pub enum SelectedInput {
     Filter,
     Search,
+    /// Prompt for the time range entries are limited to
+    TimeRange,
+    /// Prompt for the time to select the nearest entry of
+    GoToTime,
 }
 pub enum ViewMode {
     Table,
     SearchView,
     TableItem(usize /* index */),
+    FilterRules,
+    SearchResults,
+}
+
+/// Levels of the severity quick filter in the order of their keys (`1` to `8`), `None` standing for entries without a level
+pub const LEVEL_FILTERS: [Option<Severity>; 8] = [
+    Some(Severity::Trace),
+    Some(Severity::Debug),
+    Some(Severity::Info),
+    Some(Severity::Notice),
+    Some(Severity::Warn),
+    Some(Severity::Error),
+    Some(Severity::Fatal),
+    None,
+];
+
+/// Entries matching the search, computed once per search (and per change of the views) rather than on every key press.
+pub struct SearchResults {
+    /// Search text, mode, tab searched (`None` for all tabs) and views version the matches were computed for
+    key: (String, MatchMode, Option<usize>, usize),
+    /// Tab and entry index of every match, in tab then entry order
+    pub matches: Vec<(usize, usize)>,
+    /// Entries searched so far and how many there are, the matches being partial until `complete`
+    pub checked: usize,
+    pub total: usize,
+    pub complete: bool,
+}
+
+/// Entries a background job goes through between two checks of whether it was cancelled.
+const JOB_CHUNK_SIZE: usize = 50_000;
+
+/// What a filter job needs to know about one of the tabs.
+struct TabFilter {
+    tab_index: usize,
+    tab_id: usize,
+    entries: Arc<Vec<LogEntry>>,
+    /// `None` when the filter input is empty
+    query: Option<Query>,
+    expanded_lines: HashSet<usize>,
+}
+
+/// Filters applied to every tab.
+struct FilterSettings {
+    /// Query of each filter rule (`None` when it's invalid or turned off) and whether it excludes its matches
+    rules: Vec<(Option<Query>, bool)>,
+    hidden_levels: HashSet<Option<Severity>>,
+    latest_session_only: bool,
+    time_range: Option<(i64, i64)>,
+    context_lines: usize,
+    sort_by_level: bool,
+}
+
+impl FilterSettings {
+    /// Whether the entry is in the session and time range shown, entries hidden by these are never shown, not even as context.
+    fn shows(&self, item: &LogEntry, latest_session: usize) -> bool {
+        if self.latest_session_only && item.session != latest_session {
+            return false;
+        }
+
+        match self.time_range {
+            Some((start, end)) => item
+                .timestamp
+                .is_some_and(|timestamp| (start..=end).contains(&timestamp)),
+            None => true,
+        }
+    }
+
+    /// What the filter query, rules and level toggles do with the entry, counting the matches of each rule and the
+    /// levels of the entries kept by the query and rules (before the levels are filtered so that hidden levels show
+    /// how many entries they hide). Entries the query doesn't match are only looked at further `with_context`.
+    fn keeps(
+        &self,
+        item: &LogEntry,
+        query: Option<&Query>,
+        with_context: bool,
+        rule_match_counts: &mut [usize],
+        level_counts: &mut HashMap<Option<Severity>, usize>,
+    ) -> Verdict {
+        let query_match = match query {
+            Some(query) => query.matches(item),
+            None => true,
+        };
+        if !query_match && !with_context {
+            return Verdict::Hidden;
+        }
+
+        for ((rule_query, exclude), count) in self.rules.iter().zip(rule_match_counts.iter_mut()) {
+            let Some(rule_query) = rule_query else {
+                continue;
+            };
+
+            let is_match = rule_query.matches(item);
+            if is_match && query_match {
+                *count += 1;
+            }
+            if is_match == *exclude {
+                return Verdict::Hidden;
+            }
+        }
+
+        if query_match {
+            *level_counts.entry(item.severity).or_default() += 1;
+        }
+        if self.hidden_levels.contains(&item.severity) {
+            Verdict::Hidden
+        } else if query_match {
+            Verdict::Match
+        } else {
+            Verdict::Context
+        }
+    }
+}
+
+/// What the filters do with an entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Verdict {
+    Match,
+    /// Kept by the rules and level toggles but not matched by the filter query, so it can only be shown as context
+    Context,
+    Hidden,
+}
+
+/// Orders rows from the most severe level to the least (entries without a level last), keeping the
+/// order of the rows of the same level.
+fn sort_rows_by_level(rows: &mut [ViewRow], severity: impl Fn(&ViewRow) -> Option<Severity>) {
+    rows.sort_by_key(|row| Reverse(severity(row)));
+}
+
+/// What a filter job sends back as it goes through the tabs.
+enum FilterResult {
+    /// Number of entries gone through
+    Checked(usize),
+    /// Next matches of a tab, following the first `matches_before` ones (only sent when the view is in file order
+    /// without context)
+    Matches {
+        tab_index: usize,
+        matches_before: usize,
+        rows: Vec<ViewRow>,
+    },
+    /// A tab is done, its view being the `match_count` matches sent, or `view` when context is shown or it's sorted
+    Done {
+        tab_index: usize,
+        match_count: usize,
+        view: Option<Vec<ViewRow>>,
+        level_counts: HashMap<Option<Severity>, usize>,
+        rule_match_counts: Vec<usize>,
+    },
+}
+
+/// Running filter job along with what's needed to show its results.
+struct FilterJob {
+    job: Job<FilterResult>,
+    /// Line of the entry selected in each tab when the job started, or since then by the user
+    selected_lines: HashMap<usize, Option<usize>>,
+    /// Row selected in each tab when the job last changed its view, another row meaning the user moved the selection
+    selected_indices: HashMap<usize, usize>,
+    pending_tabs: usize,
+    checked: usize,
+    total: usize,
+}
+
+impl FilterJob {
+    /// Keeps the entry the user selected in the tab since the job last changed its view, if they moved it.
+    fn keep_moved_selection(&mut self, tab_index: usize, items: &TableItems, entries: &[LogEntry]) {
+        if self.selected_indices.get(&tab_index) != Some(&items.selected_item_index) {
+            let line = items.data.get(items.selected_item_index).map(|row| entries[row.index].line);
+            self.selected_lines.insert(tab_index, line);
+        }
+    }
+
+    /// Selects the entry selected before the view of the tab changed.
+    fn restore_selection(
+        &mut self,
+        tab_index: usize,
+        items: &mut TableItems,
+        entries: &[LogEntry],
+        sorted_by_level: bool,
+        tail_enabled: bool,
+    ) {
+        let selected_line = self.selected_lines.get(&tab_index).copied().flatten();
+        select_line(items, entries, selected_line, sorted_by_level, tail_enabled);
+        self.selected_indices.insert(tab_index, items.selected_item_index);
+    }
+}
+
+/// What a search job sends back: the matches found and how many entries were searched, until it's done.
+enum SearchResult {
+    Matches(Vec<(usize, usize)>, usize),
+    Done,
+}
+
+/// Rule of the filter rules panel, applied in order to the entries kept by the filter input.
+pub struct FilterRule {
+    pub query: String,
+    /// Hides the entries the query matches instead of keeping only them
+    pub exclude: bool,
+    pub enabled: bool,
+    /// Entries the query matched among the ones kept by the rules before it, `None` when the query is invalid
+    pub match_count: Option<usize>,
 }
 
 pub struct TableViewState {
@@ -73,36 +294,420 @@ pub struct App {
     copying_to_clipboard: bool,
     mouse_position: (u16, u16),
     last_key_input: Option<char>,
+    /// Whether views list the most severe entries first instead of following the order of the files
+    sort_by_level: bool,
+    parser_settings: ParserSettings,
+    latest_session_only: bool,
+    /// Zone the date column is shown in, `None` shows the dates as written in the log
+    display_zone: Option<Zone>,
+    /// Id of the tab and date of the entry marked to line up the clocks of other tabs with
+    clock_sync_mark: Option<(usize, i64)>,
+    filter_mode: MatchMode,
+    search_mode: MatchMode,
+    filter_rules: Vec<FilterRule>,
+    selected_filter_rule_index: usize,
+    /// Whether one filter and search drive every tab instead of each tab using its own
+    inputs_linked: bool,
+    /// Levels hidden by the severity quick filter, `None` hiding the entries without a level
+    hidden_levels: HashSet<Option<Severity>>,
+    /// Screen area of each level chip above the table, to toggle the level when clicked
+    level_chip_areas: Vec<(Rect, Option<Severity>)>,
+    /// Entries of every tab are limited to this range (in UTC nanoseconds, inclusive)
+    time_range: Option<(i64, i64)>,
+    /// Text of the time range / go to time prompt and why it couldn't be used
+    time_input_text: Input,
+    time_input_error: Option<String>,
+    /// Entries shown (dimmed) before and after each filter match
+    context_lines: usize,
+    search_results: Option<SearchResults>,
+    /// Whether the search results list the matches of every tab instead of the current one
+    search_results_all_tabs: bool,
+    selected_search_result_index: usize,
+    /// Incremented whenever the filtered views change, so that search results know they're stale
+    views_version: usize,
+    filter_job: Option<FilterJob>,
+    search_job: Option<Job<SearchResult>>,
+    /// Wakes the UI up when background jobs have new results
+    event_sender: Sender<Event>,
+    /// Entries of every file, which the views of the tabs point at
+    entries: EntryStore,
 }
 
-impl App {
-    pub fn new(file_paths: Vec<String>) -> App {
-        // The combined tab goes first
-        let mut tabs = vec![Tab::new(
-            "".to_owned(),
-            TableItems {
-                data: vec![],
-                selected_item_index: 0,
+/// Reads the user defined level inference rules, e.g. `{"Steam": [{"pattern": "Shutdown", "level": "warn"}]}`
+fn load_level_rules(json_level_rules: &Value) -> HashMap<String, Vec<LevelRule>> {
+    let mut level_rules = HashMap::<String, Vec<LevelRule>>::new();
+    if let Some(formats) = json_level_rules.as_object() {
+        for (format_name, rules) in formats {
+            let rules = rules
+                .as_array()
+                .map_or(&[][..], |rules| &rules[..])
+                .iter()
+                .filter_map(|rule| {
+                    let pattern = rule["pattern"].as_str()?;
+                    let severity = Severity::from_level_text(rule["level"].as_str()?)?;
+                    match LevelRule::new(pattern, severity) {
+                        Ok(rule) => Some(rule),
+                        Err(e) => {
+                            info!("Ignoring invalid level rule [{}]: {}", pattern, e);
+                            None
+                        }
+                    }
+                })
+                .collect::<Vec<LevelRule>>();
+            // Rules saved under the old name of a format and its new one both apply
+            level_rules
+                .entry(parser::current_format_name(format_name).to_owned())
+                .or_default()
+                .extend(rules);
+        }
+    }
+    level_rules
+}
+
+/// Reads time zones keyed by format name or file path, e.g. `{"Steam": "local", "C:/logs/app.log": "+01:00"}`
+fn load_zones(json_zones: &Value) -> HashMap<String, Zone> {
+    json_zones
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, zone)| {
+            let key = parser::current_format_name(key).to_owned();
+            Some((key, Zone::from_text(zone.as_str()?)?))
+        })
+        .collect()
+}
+
+/// Reads the filter rules, e.g. `[{"query": "source:steam*", "exclude": false, "enabled": true}]`
+fn load_filter_rules(json_filter_rules: &Value) -> Vec<FilterRule> {
+    json_filter_rules
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|rule| {
+            Some(FilterRule {
+                query: rule["query"].as_str()?.to_owned(),
+                exclude: rule["exclude"].as_bool().unwrap_or(false),
+                enabled: rule["enabled"].as_bool().unwrap_or(true),
+                match_count: None,
+            })
+        })
+        .collect()
+}
+
+/// Reads the levels hidden by the severity quick filter, e.g. `["Trace", "Debug", "none"]`
+fn load_hidden_levels(json_hidden_levels: &Value) -> HashSet<Option<Severity>> {
+    json_hidden_levels
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|level| match level.as_str()? {
+            "none" => Some(None),
+            level => Severity::from_level_text(level).map(Some),
+        })
+        .collect()
+}
+
+/// Entries matching the filters along with `context_lines` entries around each of them (and the ones
+/// expanded by hand), each run of entries hidden in between replaced by a separator.
+fn entries_with_context(
+    tab_id: usize,
+    entries: &[(usize, &LogEntry)],
+    is_match: &[bool],
+    context_lines: usize,
+    expanded_lines: &HashSet<usize>,
+) -> Vec<ViewRow> {
+    let mut shown = entries
+        .iter()
+        .map(|(_, item)| expanded_lines.contains(&item.line))
+        .collect::<Vec<bool>>();
+    for (index, _) in is_match.iter().enumerate().filter(|(_, is_match)| **is_match) {
+        let end = (index + context_lines + 1).min(entries.len());
+        shown[index.saturating_sub(context_lines)..end].fill(true);
+    }
+
+    let separator = |hidden_entries: &[(usize, &LogEntry)]| ViewRow {
+        tab_id,
+        index: hidden_entries[0].0,
+        kind: RowKind::Separator {
+            hidden: hidden_entries.len(),
+            last_line: hidden_entries[hidden_entries.len() - 1].1.line,
+        },
+    };
+
+    let mut rows = vec![];
+    let mut hidden_start = None;
+    for (index, (entry_index, _)) in entries.iter().enumerate() {
+        if !shown[index] {
+            hidden_start.get_or_insert(index);
+            continue;
+        }
+
+        if let Some(start) = hidden_start.take() {
+            rows.push(separator(&entries[start..index]));
+        }
+
+        rows.push(ViewRow {
+            tab_id,
+            index: *entry_index,
+            kind: if is_match[index] {
+                RowKind::Match
+            } else {
+                RowKind::Context
             },
-            TabType::Combined,
-        )];
+        });
+    }
 
-        tabs.append(
-            &mut file_paths
+    if let Some(start) = hidden_start {
+        rows.push(separator(&entries[start..]));
+    }
+
+    rows
+}
+
+/// Filters the entries of each tab in chunks, sending the matches found so far unless context has to be added
+/// around them or they're sorted by level.
+fn run_filter_job(context: &JobContext<FilterResult>, tab_filters: Vec<TabFilter>, settings: FilterSettings) {
+    for tab_filter in tab_filters {
+        let latest_session = tab_filter
+            .entries
+            .iter()
+            .map(|item| item.session)
+            .max()
+            .unwrap_or_default();
+        let entries = tab_filter
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| settings.shows(item, latest_session))
+            .collect::<Vec<(usize, &LogEntry)>>();
+        if !context.send(FilterResult::Checked(tab_filter.entries.len() - entries.len())) {
+            return;
+        }
+
+        // Context stands for the entries around a match in the file, which means nothing once sorted by level
+        let with_context = !settings.sort_by_level
+            && (settings.context_lines > 0 || !tab_filter.expanded_lines.is_empty());
+        let in_file_order = !with_context && !settings.sort_by_level;
+        let mut level_counts = HashMap::new();
+        let mut rule_match_counts = vec![0; settings.rules.len()];
+        let mut verdicts = Vec::with_capacity(entries.len());
+        let mut match_count = 0;
+        for chunk in entries.chunks(JOB_CHUNK_SIZE) {
+            if context.cancelled() {
+                return;
+            }
+
+            let chunk_start = verdicts.len();
+            verdicts.extend(chunk.iter().map(|(_, item)| {
+                settings.keeps(
+                    item,
+                    tab_filter.query.as_ref(),
+                    with_context,
+                    &mut rule_match_counts,
+                    &mut level_counts,
+                )
+            }));
+            let matches = chunk
                 .iter()
-                .map(|file_path| {
-                    let table_items = TableItems {
-                        data: parser::parse_log_by_path(file_path).unwrap_or_default(),
-                        selected_item_index: 0,
-                    };
-                    Tab::new(file_path.to_owned(), table_items, TabType::Normal)
+                .zip(&verdicts[chunk_start..])
+                .filter(|(_, verdict)| in_file_order && **verdict == Verdict::Match)
+                .map(|((index, _), _)| ViewRow {
+                    tab_id: tab_filter.tab_id,
+                    index: *index,
+                    kind: RowKind::Match,
                 })
-                .collect::<Vec<Tab>>(),
-        );
+                .collect::<Vec<ViewRow>>();
+            if !matches.is_empty() {
+                let matches_before = match_count;
+                match_count += matches.len();
+                let sent = context.send(FilterResult::Matches {
+                    tab_index: tab_filter.tab_index,
+                    matches_before,
+                    rows: matches,
+                });
+                if !sent {
+                    return;
+                }
+            }
+            if !context.send(FilterResult::Checked(chunk.len())) {
+                return;
+            }
+        }
 
+        let view = if with_context {
+            // Entries hidden by the rules or level toggles aren't shown as context either
+            let (candidates, is_match) = entries
+                .iter()
+                .zip(&verdicts)
+                .filter(|(_, verdict)| **verdict != Verdict::Hidden)
+                .map(|(entry, verdict)| (*entry, *verdict == Verdict::Match))
+                .unzip::<_, _, Vec<(usize, &LogEntry)>, Vec<bool>>();
+            Some(entries_with_context(
+                tab_filter.tab_id,
+                &candidates,
+                &is_match,
+                settings.context_lines,
+                &tab_filter.expanded_lines,
+            ))
+        } else if settings.sort_by_level {
+            let mut rows = entries
+                .iter()
+                .zip(&verdicts)
+                .filter(|(_, verdict)| **verdict == Verdict::Match)
+                .map(|((index, _), _)| ViewRow {
+                    tab_id: tab_filter.tab_id,
+                    index: *index,
+                    kind: RowKind::Match,
+                })
+                .collect::<Vec<ViewRow>>();
+            sort_rows_by_level(&mut rows, |row| tab_filter.entries[row.index].severity);
+            Some(rows)
+        } else {
+            None
+        };
+        let sent = context.send(FilterResult::Done {
+            tab_index: tab_filter.tab_index,
+            match_count,
+            view,
+            level_counts,
+            rule_match_counts,
+        });
+        if !sent {
+            return;
+        }
+    }
+}
+
+/// Searches the views (tab index and rows) in chunks, sending the matches as they're found.
+fn run_search_job(
+    context: &JobContext<SearchResult>,
+    matcher: Matcher,
+    store: EntryStore,
+    views: Vec<(usize, Arc<Vec<ViewRow>>)>,
+) {
+    for (tab_index, rows) in views {
+        for (chunk_index, chunk) in rows.chunks(JOB_CHUNK_SIZE).enumerate() {
+            if context.cancelled() {
+                return;
+            }
+
+            let matches = chunk
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| !matches!(row.kind, RowKind::Separator { .. }))
+                .filter(|(_, row)| matcher.is_match(&store.entry(row)[LogEntryIndices::Log as usize]))
+                .map(|(index, _)| (tab_index, chunk_index * JOB_CHUNK_SIZE + index))
+                .collect();
+            if !context.send(SearchResult::Matches(matches, chunk.len())) {
+                return;
+            }
+        }
+    }
+
+    context.send(SearchResult::Done);
+}
+
+/// What the combined tab orders rows by: the order timestamp of the entry (see [`LogEntry::order_timestamp`]),
+/// the entries of files without any full date going last, then the tab (tab ids grow with the tabs opened)
+/// and the entry, so that rows of the same date end up in the same order however they're merged.
+fn row_order_key(entries: &EntryStore, row: &ViewRow) -> (bool, Option<i64>, usize, usize) {
+    let order_timestamp = entries.entry(row).order_timestamp;
+    (order_timestamp.is_none(), order_timestamp, row.tab_id, row.index)
+}
+
+/// Merges views that are each in date order into one, going through every view once instead of sorting
+/// all the rows again. Separators stand for entries of their own file, so they're left out.
+fn merge_rows(entries: &EntryStore, views: &[&[ViewRow]]) -> Vec<ViewRow> {
+    // Position of the next row of a view that isn't a separator, with its key
+    let next_row = |view_index: usize, position: usize| {
+        let view = views[view_index];
+        let position = position
+            + view[position..]
+                .iter()
+                .position(|row| !matches!(row.kind, RowKind::Separator { .. }))?;
+        Some(Reverse((row_order_key(entries, &view[position]), view_index, position)))
+    };
+    // The next row of each view, earliest first
+    let mut next_rows = (0..views.len())
+        .filter_map(|view_index| next_row(view_index, 0))
+        .collect::<BinaryHeap<_>>();
+
+    let mut rows = Vec::with_capacity(views.iter().map(|view| view.len()).sum());
+    while let Some(Reverse((_, view_index, position))) = next_rows.pop() {
+        rows.push(views[view_index][position]);
+        next_rows.extend(next_row(view_index, position + 1));
+    }
+
+    rows
+}
+
+/// Whether `new` only adds entries after the ones of `old`. The last entry has to be the same
+/// too, as lines added to the file can belong to it.
+fn is_append(old: &[LogEntry], new: &[LogEntry]) -> bool {
+    match old.last() {
+        None => true,
+        Some(last) => new
+            .get(old.len() - 1)
+            .is_some_and(|entry| entry.line == last.line && entry.columns == last.columns),
+    }
+}
+
+/// Selects the entry of the line (or the next one left) instead of jumping back to the start, or the last one when tailing.
+fn select_line(
+    items: &mut TableItems,
+    entries: &[LogEntry],
+    line: Option<usize>,
+    sorted_by_level: bool,
+    tail_enabled: bool,
+) {
+    let last_index = items.data.len().saturating_sub(1);
+    let line_index = |line: usize| {
+        if sorted_by_level {
+            // Views sorted by level aren't in line order
+            let rows = items.data.iter();
+            rows.clone()
+                .position(|row| entries[row.index].line == line)
+                .or_else(|| rows.clone().position(|row| entries[row.index].line > line))
+        } else {
+            let index = items.data.partition_point(|row| entries[row.index].line < line);
+            (index < items.data.len()).then_some(index)
+        }
+    };
+    items.selected_item_index = match line {
+        _ if tail_enabled => last_index,
+        Some(line) => line_index(line).unwrap_or(last_index),
+        None => 0,
+    };
+}
+
+/// Reads the clock offsets keyed by file path, saved in milliseconds
+fn load_clock_offsets(json_clock_offsets: &Value) -> HashMap<String, i64> {
+    json_clock_offsets
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(file_path, offset)| {
+            Some((file_path.to_owned(), (offset.as_f64()? * 1_000_000.0).round() as i64))
+        })
+        .collect()
+}
+
+impl App {
+    pub fn new(file_paths: Vec<String>, event_sender: Sender<Event>) -> App {
         let mut search_input_text = String::new();
         let mut filter_input_text = String::new();
         let mut tail_enabled = false;
+        let mut sort_by_level = false;
+        let mut parser_settings = ParserSettings::default();
+        let mut display_zone = None;
+        let mut filter_mode = MatchMode::default();
+        let mut search_mode = MatchMode::default();
+        let mut filter_rules = vec![];
+        let mut inputs_linked = true;
+        let mut hidden_levels = HashSet::new();
+        let mut context_lines = 0;
+        let mut tab_inputs = serde_json::Map::new();
+        let mut config_file_paths = vec![];
 
         // Load config file saved the last session before exit
         if let Ok(mut config_file) = std::fs::File::open(format!(
@@ -114,32 +719,26 @@ impl App {
             let mut str_config_file = String::new();
             if config_file.read_to_string(&mut str_config_file).is_ok() {
                 let mut json_config_file: Value = serde_json::from_str(&str_config_file).unwrap();
-                tabs.append(
-                    &mut json_config_file["tabs"]
-                        .as_array_mut()
-                        .unwrap()
-                        .iter_mut()
-                        .filter(|file_path| !file_path.to_string().is_empty())
-                        .map(|file_path| {
-                            let mut file_path = file_path.to_string();
-
-                            // sometimes command line quotes are included so here we strip the out
-                            if file_path.starts_with('"') && file_path.len() > 1 {
-                                file_path = file_path[1..].to_string();
-                            }
-
-                            if file_path.ends_with('"') && file_path.len() > 1 {
-                                file_path = file_path[..file_path.len() - 1].to_string();
-                            }
-
-                            let table_items = TableItems {
-                                data: parser::parse_log_by_path(&file_path).unwrap_or_default(),
-                                selected_item_index: 0,
-                            };
-                            Tab::new(file_path, table_items, TabType::Normal)
-                        })
-                        .collect::<Vec<Tab>>(),
-                );
+                config_file_paths = json_config_file["tabs"]
+                    .as_array_mut()
+                    .unwrap()
+                    .iter_mut()
+                    .filter(|file_path| !file_path.to_string().is_empty())
+                    .map(|file_path| {
+                        let mut file_path = file_path.to_string();
+
+                        // sometimes command line quotes are included so here we strip the out
+                        if file_path.starts_with('"') && file_path.len() > 1 {
+                            file_path = file_path[1..].to_string();
+                        }
+
+                        if file_path.ends_with('"') && file_path.len() > 1 {
+                            file_path = file_path[..file_path.len() - 1].to_string();
+                        }
+
+                        file_path
+                    })
+                    .collect::<Vec<String>>();
                 filter_input_text = json_config_file["filter_input_text"]
                     .as_str()
                     .unwrap_or("")
@@ -149,10 +748,43 @@ impl App {
                     .unwrap_or("")
                     .to_string();
                 tail_enabled = json_config_file["tail"].as_bool().unwrap_or(false);
+                filter_mode = json_config_file["filter_mode"]
+                    .as_str()
+                    .and_then(MatchMode::from_text)
+                    .unwrap_or_default();
+                search_mode = json_config_file["search_mode"]
+                    .as_str()
+                    .and_then(MatchMode::from_text)
+                    .filter(|mode| *mode != MatchMode::Query)
+                    .unwrap_or_default();
+                sort_by_level = json_config_file["sort_by_level"].as_bool().unwrap_or(false);
+                parser_settings.infer_levels =
+                    json_config_file["infer_levels"].as_bool().unwrap_or(false);
+                parser_settings.level_rules = load_level_rules(&json_config_file["level_rules"]);
+                parser_settings.format_zones = load_zones(&json_config_file["format_zones"]);
+                parser_settings.tab_zones = load_zones(&json_config_file["tab_zones"]);
+                parser_settings.clock_offsets =
+                    load_clock_offsets(&json_config_file["clock_offsets"]);
+                display_zone = json_config_file["display_zone"]
+                    .as_str()
+                    .and_then(Zone::from_text);
+                filter_rules = load_filter_rules(&json_config_file["filter_rules"]);
+                inputs_linked = json_config_file["inputs_linked"].as_bool().unwrap_or(true);
+                hidden_levels = load_hidden_levels(&json_config_file["hidden_levels"]);
+                context_lines = json_config_file["context_lines"].as_u64().unwrap_or(0) as usize;
+                if let Some(json_tab_inputs) = json_config_file["tab_inputs"].as_object() {
+                    tab_inputs = json_tab_inputs.clone();
+                }
             }
         }
 
         let mut files = HashSet::new();
+        let file_paths = file_paths
+            .into_iter()
+            .chain(config_file_paths)
+            .filter(move |file_path| files.insert(file_path.clone()))
+            .collect::<Vec<String>>();
+
         let mut app = App {
             running: true,
             table_view_state: TableViewState {
@@ -160,17 +792,8 @@ impl App {
                 position: None,
             },
             view_mode: vec![ViewMode::Table].into(),
-            tabs: tabs
-                .into_iter()
-                .filter(move |tab| {
-                    if files.contains(&tab.file_path) {
-                        return false;
-                    }
-
-                    files.insert(tab.file_path.clone());
-                    true
-                })
-                .collect::<Vec<Tab>>(),
+            // The combined tab goes first
+            tabs: vec![Tab::new("".to_owned(), TabType::Combined)],
             selected_tab_index: 0,
             selected_input: None,
             filter_input_text: Input::new(filter_input_text),
@@ -180,9 +803,46 @@ impl App {
             copying_to_clipboard: false,
             mouse_position: (0, 0),
             last_key_input: None,
+            sort_by_level,
+            parser_settings,
+            latest_session_only: false,
+            display_zone,
+            clock_sync_mark: None,
+            filter_mode,
+            search_mode,
+            filter_rules,
+            selected_filter_rule_index: 0,
+            inputs_linked,
+            hidden_levels,
+            level_chip_areas: vec![],
+            time_range: None,
+            time_input_text: Input::default(),
+            time_input_error: None,
+            context_lines,
+            search_results: None,
+            search_results_all_tabs: false,
+            selected_search_result_index: 0,
+            views_version: 0,
+            filter_job: None,
+            search_job: None,
+            event_sender,
+            entries: EntryStore::default(),
         };
 
-        app.reload_combined_tab();
+        for file_path in file_paths {
+            let entries = parser::parse_log_by_path(&file_path, &app.parser_settings).unwrap_or_default();
+            app.add_tab(file_path, entries);
+        }
+        app.selected_tab_index = 0;
+
+        // Filters and searches of each tab, used when the inputs aren't linked
+        for tab in &mut app.tabs {
+            let inputs = tab_inputs.get(&tab.file_path).cloned().unwrap_or_default();
+            tab.filter_input_text = Input::new(inputs["filter"].as_str().unwrap_or("").to_owned());
+            tab.search_input_text = Input::new(inputs["search"].as_str().unwrap_or("").to_owned());
+        }
+
+        app.filter_by_current_input();
         app
     }
 
@@ -214,6 +874,66 @@ impl App {
         &mut self.tabs
     }
 
+    /// Entry a row of a view points at.
+    pub fn entry(&self, row: &ViewRow) -> &LogEntry {
+        self.entries.entry(row)
+    }
+
+    /// Opens a tab showing the entries of a file (until the filters apply to them) and selects it.
+    pub fn add_tab(&mut self, file_path: String, entries: Vec<LogEntry>) {
+        let mut tab = Tab::new(file_path, TabType::Normal);
+        tab.filtered_view_items = TableItems::all(tab.id, entries.len());
+        self.entries.insert(tab.id, entries);
+        self.tabs.push(tab);
+        self.selected_tab_index = self.tabs.len() - 1;
+    }
+
+    /// Closes the selected tab along with its entries, the combined tab can't be closed.
+    pub fn close_selected_tab(&mut self) {
+        let Some(tab) = self.tabs.get(self.selected_tab_index) else {
+            return;
+        };
+        if matches!(tab.tab_type, TabType::Combined) {
+            return;
+        }
+
+        let tab_id = tab.id;
+        if self.clock_sync_mark.is_some_and(|(mark_tab_id, _)| mark_tab_id == tab_id) {
+            self.clock_sync_mark = None;
+        }
+        self.entries.remove(tab_id);
+        self.tabs.remove(self.selected_tab_index);
+        self.selected_tab_index = self.selected_tab_index.min(self.tabs.len().saturating_sub(1));
+        self.drop_rows_of_tab(tab_id);
+        // Restarts the filter job, which would otherwise send the results of the old tab indices
+        self.filter_by_current_input();
+    }
+
+    /// Stops the filter and search jobs, waiting for them so that the entries they went through can be
+    /// changed without copying them. The views are left as the filter job got them, until filtering again.
+    fn cancel_jobs(&mut self) {
+        if let Some(filter_job) = self.filter_job.take() {
+            filter_job.job.cancel();
+        }
+        if let Some(search_job) = self.search_job.take() {
+            search_job.cancel();
+        }
+        self.search_results = None;
+    }
+
+    /// Removes the rows of a tab from the combined tab and the search results, for when its entries are gone
+    /// before the filter job rebuilds the views (rows pointing at them can't wait for it).
+    fn drop_rows_of_tab(&mut self, tab_id: usize) {
+        let combined_items = &mut self.tabs[COMBINED_TAB_INDEX].filtered_view_items;
+        Arc::make_mut(&mut combined_items.data).retain(|row| row.tab_id != tab_id);
+        combined_items.selected_item_index = combined_items
+            .selected_item_index
+            .min(combined_items.data.len().saturating_sub(1));
+        self.search_job = None;
+        self.search_results = None;
+        self.views_version += 1;
+    }
+
     pub fn running(&self) -> &bool {
         &self.running
     }
@@ -226,10 +946,6 @@ impl App {
         self.selected_tab_index
     }
 
-    pub fn selected_tab_index_mut(&mut self) -> &mut usize {
-        &mut self.selected_tab_index
-    }
-
     pub fn selected_input(&self) -> &Option<SelectedInput> {
         &self.selected_input
     }
@@ -250,35 +966,754 @@ impl App {
         self.tail_enabled
     }
 
-    pub fn set_tail_enabled(&mut self, tail_enabled: bool) {
-        self.tail_enabled = tail_enabled;
+    pub fn set_tail_enabled(&mut self, tail_enabled: bool) {
+        self.tail_enabled = tail_enabled;
+    }
+
+    pub fn parser_settings(&self) -> &ParserSettings {
+        &self.parser_settings
+    }
+
+    pub fn infer_levels(&self) -> bool {
+        self.parser_settings.infer_levels
+    }
+
+    pub fn set_infer_levels(&mut self, infer_levels: bool) {
+        self.parser_settings.infer_levels = infer_levels;
+        self.cancel_jobs();
+        for tab in &mut self.tabs {
+            if matches!(tab.tab_type, TabType::Combined) {
+                continue;
+            }
+
+            parser::apply_level_inference(self.entries.get_mut(tab.id), &self.parser_settings);
+        }
+
+        self.filter_by_current_input();
+    }
+
+    pub fn latest_session_only(&self) -> bool {
+        self.latest_session_only
+    }
+
+    pub fn set_latest_session_only(&mut self, latest_session_only: bool) {
+        self.latest_session_only = latest_session_only;
+        self.filter_by_current_input();
+    }
+
+    pub fn filter_mode(&self) -> MatchMode {
+        self.filter_mode
+    }
+
+    pub fn cycle_filter_mode(&mut self) {
+        self.filter_mode = self.filter_mode.next();
+        self.filter_by_current_input();
+    }
+
+    pub fn search_mode(&self) -> MatchMode {
+        self.search_mode
+    }
+
+    pub fn cycle_search_mode(&mut self) {
+        // Queries select entries, they don't point at text to highlight
+        self.search_mode = match self.search_mode.next() {
+            MatchMode::Query => MatchMode::Query.next(),
+            mode => mode,
+        };
+    }
+
+    pub fn filter_query(&self) -> anyhow::Result<Query> {
+        Query::new(
+            self.filter_input_text().value(),
+            self.filter_mode,
+            self.display_zone.unwrap_or(Zone::Local),
+        )
+    }
+
+    pub fn filter_rules(&self) -> &Vec<FilterRule> {
+        &self.filter_rules
+    }
+
+    pub fn selected_filter_rule_index(&self) -> usize {
+        self.selected_filter_rule_index
+    }
+
+    /// Shows the filter rules panel (or hides it when it's already shown).
+    pub fn toggle_filter_rules(&mut self) {
+        if matches!(self.view_mode.back(), Some(ViewMode::FilterRules)) {
+            self.view_mode.pop_back();
+        } else {
+            self.view_mode.push_back(ViewMode::FilterRules);
+        }
+    }
+
+    pub fn select_next_filter_rule(&mut self) {
+        if self.selected_filter_rule_index + 1 < self.filter_rules.len() {
+            self.selected_filter_rule_index += 1;
+        }
+    }
+
+    pub fn select_previous_filter_rule(&mut self) {
+        self.selected_filter_rule_index = self.selected_filter_rule_index.saturating_sub(1);
+    }
+
+    fn add_filter_rule(&mut self, query: String, exclude: bool) {
+        self.filter_rules.push(FilterRule {
+            query,
+            exclude,
+            enabled: true,
+            match_count: None,
+        });
+        self.selected_filter_rule_index = self.filter_rules.len() - 1;
+        self.filter_by_current_input();
+    }
+
+    /// Moves the text of the filter input into a new include rule.
+    pub fn add_filter_rule_from_input(&mut self) {
+        let query = self.filter_input_text().value().trim().to_owned();
+        if query.is_empty() || Query::new(&query, MatchMode::Query, Zone::Local).is_err() {
+            return;
+        }
+
+        self.filter_input_text_mut().reset();
+        self.add_filter_rule(query, false);
+    }
+
+    /// Adds an exclude rule hiding the entries whose message looks like the one of the selected entry.
+    pub fn hide_entries_like_selected(&mut self) {
+        let Some(tab) = self.tabs.get(self.selected_tab_index) else {
+            return;
+        };
+        let items = &tab.filtered_view_items;
+        let Some(row) = items
+            .data
+            .get(items.selected_item_index)
+            .filter(|row| !matches!(row.kind, RowKind::Separator { .. }))
+        else {
+            return;
+        };
+
+        let query = like_query(&self.entries.entry(row)[LogEntryIndices::Log as usize]);
+        self.add_filter_rule(query, true);
+    }
+
+    pub fn toggle_selected_filter_rule(&mut self) {
+        if let Some(rule) = self.filter_rules.get_mut(self.selected_filter_rule_index) {
+            rule.enabled = !rule.enabled;
+            self.filter_by_current_input();
+        }
+    }
+
+    /// Switches the selected rule between keeping and hiding the entries it matches.
+    pub fn flip_selected_filter_rule(&mut self) {
+        if let Some(rule) = self.filter_rules.get_mut(self.selected_filter_rule_index) {
+            rule.exclude = !rule.exclude;
+            self.filter_by_current_input();
+        }
+    }
+
+    pub fn remove_selected_filter_rule(&mut self) {
+        if self.selected_filter_rule_index < self.filter_rules.len() {
+            self.filter_rules.remove(self.selected_filter_rule_index);
+            self.selected_filter_rule_index = self
+                .selected_filter_rule_index
+                .min(self.filter_rules.len().saturating_sub(1));
+            self.filter_by_current_input();
+        }
+    }
+
+    /// Moves the selected rule earlier (`up`) or later in the order the rules are applied in.
+    pub fn move_selected_filter_rule(&mut self, up: bool) {
+        let index = self.selected_filter_rule_index;
+        let other_index = if up {
+            index.wrapping_sub(1)
+        } else {
+            index + 1
+        };
+        if index < self.filter_rules.len() && other_index < self.filter_rules.len() {
+            self.filter_rules.swap(index, other_index);
+            self.selected_filter_rule_index = other_index;
+            self.filter_by_current_input();
+        }
+    }
+
+    pub fn level_hidden(&self, level: Option<Severity>) -> bool {
+        self.hidden_levels.contains(&level)
+    }
+
+    /// Shows or hides the entries of a level (`None` for the entries without one).
+    pub fn toggle_level(&mut self, level: Option<Severity>) {
+        if !self.hidden_levels.remove(&level) {
+            self.hidden_levels.insert(level);
+        }
+        self.filter_by_current_input();
+    }
+
+    pub fn show_all_levels(&mut self) {
+        self.hidden_levels.clear();
+        self.filter_by_current_input();
+    }
+
+    /// Shows only warnings and worse, or every level again when that's already the case.
+    pub fn toggle_warnings_and_errors_only(&mut self) {
+        let below_warn = LEVEL_FILTERS
+            .into_iter()
+            .filter(|level| !level.is_some_and(|severity| severity >= Severity::Warn))
+            .collect::<HashSet<Option<Severity>>>();
+        self.hidden_levels = if self.hidden_levels == below_warn {
+            HashSet::new()
+        } else {
+            below_warn
+        };
+        self.filter_by_current_input();
+    }
+
+    pub fn level_chip_areas_mut(&mut self) -> &mut Vec<(Rect, Option<Severity>)> {
+        &mut self.level_chip_areas
+    }
+
+    /// Toggles the level whose chip was clicked, returning whether the click was on a chip.
+    pub fn handle_level_chip_click(&mut self) -> bool {
+        let (column, row) = self.mouse_position;
+        let clicked_level = self.level_chip_areas.iter().find_map(|(area, level)| {
+            (row == area.y && column >= area.x && column < area.x + area.width).then_some(*level)
+        });
+
+        match clicked_level {
+            Some(level) => {
+                self.toggle_level(level);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn context_lines(&self) -> usize {
+        self.context_lines
+    }
+
+    /// Shows more (or fewer) entries around the filter matches, dropping the expanded ones when there are none left.
+    pub fn change_context_lines(&mut self, delta: isize) {
+        self.context_lines = self.context_lines.saturating_add_signed(delta);
+        if self.context_lines == 0 {
+            for tab in &mut self.tabs {
+                tab.expanded_lines.clear();
+            }
+        }
+        self.filter_by_current_input();
+    }
+
+    /// Shows the first and last few entries hidden behind the selected separator (or all of them when there are few).
+    pub fn expand_selected_separator(&mut self) {
+        const EXPAND_STEP: usize = 10;
+
+        let Some(tab) = self.tabs.get_mut(self.selected_tab_index) else {
+            return;
+        };
+        let items = &tab.filtered_view_items;
+        let Some(row) = items.data.get(items.selected_item_index) else {
+            return;
+        };
+        let RowKind::Separator { hidden, last_line } = row.kind else {
+            return;
+        };
+        let first_line = self.entries.entry(row).line;
+
+        let hidden_lines = self
+            .entries
+            .get(tab.id)
+            .iter()
+            .map(|item| item.line)
+            .filter(|line| (first_line..=last_line).contains(line))
+            .collect::<Vec<usize>>();
+        if hidden <= 2 * EXPAND_STEP || hidden_lines.len() <= 2 * EXPAND_STEP {
+            tab.expanded_lines.extend(hidden_lines);
+        } else {
+            tab.expanded_lines.extend(&hidden_lines[..EXPAND_STEP]);
+            tab.expanded_lines.extend(&hidden_lines[hidden_lines.len() - EXPAND_STEP..]);
+        }
+
+        self.filter_by_current_input();
+    }
+
+    /// Searches again (on a worker thread, see [`Self::update_jobs`]) when the search or the views changed since the last search.
+    pub fn update_search_results(&mut self) {
+        let search = self.search_input_text().value().to_owned();
+        let key = (
+            search.clone(),
+            self.search_mode,
+            (!self.search_results_all_tabs).then_some(self.selected_tab_index),
+            self.views_version,
+        );
+        if self.search_results.as_ref().is_some_and(|results| results.key == key) {
+            return;
+        }
+
+        // Replacing the job cancels the stale one
+        self.search_job = None;
+        self.search_results = None;
+        // Views change as the filter job goes, so the search starts once it's done
+        if self.filter_job.is_some() {
+            return;
+        }
+
+        let Ok(matcher) = Matcher::new(&search, self.search_mode) else {
+            return;
+        };
+        if matcher.is_empty() {
+            return;
+        }
+
+        let views = self
+            .tabs
+            .iter()
+            .enumerate()
+            .filter(|(tab_index, tab)| match key.2 {
+                Some(selected_tab_index) => *tab_index == selected_tab_index,
+                // The combined tab only repeats the entries of the other tabs
+                None => !matches!(tab.tab_type, TabType::Combined),
+            })
+            .map(|(tab_index, tab)| (tab_index, Arc::clone(&tab.filtered_view_items.data)))
+            .collect::<Vec<(usize, Arc<Vec<ViewRow>>)>>();
+        let total = views.iter().map(|(_, rows)| rows.len()).sum();
+        let store = self.entries.clone();
+        self.search_job = Some(Job::spawn(self.event_sender.clone(), move |context| {
+            run_search_job(context, matcher, store, views)
+        }));
+        self.search_results = Some(SearchResults {
+            key,
+            matches: vec![],
+            checked: 0,
+            total,
+            complete: false,
+        });
+    }
+
+    pub fn search_results(&self) -> Option<&SearchResults> {
+        self.search_results.as_ref()
+    }
+
+    /// Which match (starting from 1) the selected entry is, if it is one, and how many matches there are.
+    pub fn search_match_position(&self) -> Option<(Option<usize>, usize)> {
+        let results = self.search_results.as_ref()?;
+        let selected = (
+            self.selected_tab_index,
+            self.tabs
+                .get(self.selected_tab_index)?
+                .filtered_view_items
+                .selected_item_index,
+        );
+        let position = results.matches.binary_search(&selected).ok().map(|index| index + 1);
+        Some((position, results.matches.len()))
+    }
+
+    pub fn search_results_all_tabs(&self) -> bool {
+        self.search_results_all_tabs
+    }
+
+    pub fn toggle_search_results_all_tabs(&mut self) {
+        self.search_results_all_tabs = !self.search_results_all_tabs;
+        self.selected_search_result_index = 0;
+        self.update_search_results();
+    }
+
+    pub fn selected_search_result_index(&self) -> usize {
+        self.selected_search_result_index
+    }
+
+    /// Shows the search results pane (or hides it when it's already shown).
+    pub fn toggle_search_results(&mut self) {
+        if matches!(self.view_mode.back(), Some(ViewMode::SearchResults)) {
+            self.view_mode.pop_back();
+            return;
+        }
+
+        self.update_search_results();
+        // Start from the selected entry (or the first match after it)
+        if let (Some(results), Some(tab)) = (&self.search_results, self.tabs.get(self.selected_tab_index)) {
+            let selected = (self.selected_tab_index, tab.filtered_view_items.selected_item_index);
+            self.selected_search_result_index = results
+                .matches
+                .partition_point(|position| *position < selected)
+                .min(results.matches.len().saturating_sub(1));
+        }
+        self.view_mode.push_back(ViewMode::SearchResults);
+    }
+
+    /// Moves the selection of the search results by `delta` rows.
+    pub fn move_search_result_selection(&mut self, delta: isize) {
+        let count = self
+            .search_results
+            .as_ref()
+            .map_or(0, |results| results.matches.len());
+        self.selected_search_result_index = self
+            .selected_search_result_index
+            .saturating_add_signed(delta)
+            .min(count.saturating_sub(1));
+    }
+
+    /// Selects the entry of the selected search result (in its tab) and closes the results.
+    pub fn jump_to_selected_search_result(&mut self) {
+        let Some(&(tab_index, item_index)) = self
+            .search_results
+            .as_ref()
+            .and_then(|results| results.matches.get(self.selected_search_result_index))
+        else {
+            return;
+        };
+
+        self.selected_tab_index = tab_index;
+        self.tabs[tab_index].filtered_view_items.selected_item_index = item_index;
+        self.table_view_state
+            .state
+            .select(Some(self.calculate_position_in_view_buffer()));
+        if matches!(self.view_mode.back(), Some(ViewMode::SearchResults)) {
+            self.view_mode.pop_back();
+        }
+    }
+
+    pub fn time_range(&self) -> Option<(i64, i64)> {
+        self.time_range
+    }
+
+    pub fn time_input_text(&self) -> &Input {
+        &self.time_input_text
+    }
+
+    pub fn time_input_text_mut(&mut self) -> &mut Input {
+        &mut self.time_input_text
+    }
+
+    pub fn time_input_error(&self) -> Option<&String> {
+        self.time_input_error.as_ref()
+    }
+
+    /// Focuses the time range (or go to time) prompt.
+    pub fn open_time_input(&mut self, input: SelectedInput) {
+        self.time_input_text.reset();
+        self.time_input_error = None;
+        self.selected_input = Some(input);
+    }
+
+    /// Uses the text of the time prompt, which stays open with an error when it can't be read.
+    pub fn apply_time_input(&mut self) {
+        let text = self.time_input_text.value().trim().to_owned();
+        let result = match self.selected_input {
+            Some(SelectedInput::TimeRange) => self.parse_time_range(&text).map(|time_range| {
+                self.time_range = time_range;
+                self.filter_by_current_input();
+            }),
+            Some(SelectedInput::GoToTime) => self
+                .parse_time(&text)
+                .map(|timestamp| self.go_to_time(timestamp)),
+            _ => return,
+        };
+
+        match result {
+            Ok(()) => self.selected_input = None,
+            Err(e) => self.time_input_error = Some(e.to_string()),
+        }
+    }
+
+    /// Reads a date, or a duration from the selected entry (e.g. `+5m` or `-30s`).
+    fn parse_time(&self, text: &str) -> anyhow::Result<i64> {
+        let date_zone = self.display_zone.unwrap_or(Zone::Local);
+        if let Some(timestamp) = parse_date(text, date_zone) {
+            return Ok(timestamp);
+        }
+
+        let (after, duration) = if let Some(duration) = text.strip_prefix('+') {
+            (true, duration)
+        } else if let Some(duration) = text.strip_prefix('-') {
+            (false, duration)
+        } else {
+            anyhow::bail!("expected a date or a duration from the selected entry like +5m");
+        };
+        let duration = parse_duration(duration)
+            .ok_or_else(|| anyhow::anyhow!("invalid duration [{}]", duration))?;
+        let timestamp = self
+            .selected_entry_timestamp()
+            .ok_or_else(|| anyhow::anyhow!("the selected entry has no date"))?;
+        if after {
+            timestamp.checked_add(duration)
+        } else {
+            timestamp.checked_sub(duration)
+        }
+        .ok_or_else(|| anyhow::anyhow!("the time is out of range"))
+    }
+
+    /// Reads `start..end` (either side can be left out), or a duration around the selected entry:
+    /// `30s` (or `±30s`) on both sides, `+30s` after it and `-30s` before it. Nothing clears the range.
+    fn parse_time_range(&self, text: &str) -> anyhow::Result<Option<(i64, i64)>> {
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        if let Some((start, end)) = text.split_once("..") {
+            let date_zone = self.display_zone.unwrap_or(Zone::Local);
+            let parse_side = |side: &str, open: i64| {
+                if side.trim().is_empty() {
+                    return Ok(open);
+                }
+                parse_date(side, date_zone)
+                    .ok_or_else(|| anyhow::anyhow!("invalid date [{}]", side.trim()))
+            };
+            let (start, end) = (parse_side(start, i64::MIN)?, parse_side(end, i64::MAX)?);
+            if start > end {
+                anyhow::bail!("the range ends before it starts");
+            }
+            return Ok(Some((start, end)));
+        }
+
+        let (before, after, duration) = match text.chars().next() {
+            Some('+') => (false, true, &text[1..]),
+            Some('-') => (true, false, &text[1..]),
+            Some('±') => (true, true, &text['±'.len_utf8()..]),
+            _ => (true, true, text),
+        };
+        let duration = parse_duration(duration).ok_or_else(|| {
+            anyhow::anyhow!("expected start..end or a duration around the selected entry like ±30s")
+        })?;
+        let timestamp = self
+            .selected_entry_timestamp()
+            .ok_or_else(|| anyhow::anyhow!("the selected entry has no date"))?;
+        let (Some(start), Some(end)) = (
+            timestamp.checked_sub(if before { duration } else { 0 }),
+            timestamp.checked_add(if after { duration } else { 0 }),
+        ) else {
+            anyhow::bail!("the time is out of range");
+        };
+        Ok(Some((start, end)))
+    }
+
+    /// Selects the entry of the current tab closest to the time.
+    pub fn go_to_time(&mut self, timestamp: i64) {
+        let Some(tab) = self.tabs.get_mut(self.selected_tab_index) else {
+            return;
+        };
+        let items = &mut tab.filtered_view_items;
+        if items.data.is_empty() {
+            return;
+        }
+
+        let entries = &self.entries;
+        let distance = |index: usize| {
+            items
+                .data
+                .get(index)
+                .and_then(|row| entries.entry(row).timestamp)
+                .map_or(i64::MAX, |item_timestamp| item_timestamp.saturating_sub(timestamp).saturating_abs())
+        };
+        items.selected_item_index = if self.sort_by_level {
+            // Views sorted by level aren't in date order
+            (0..items.data.len()).min_by_key(|index| distance(*index)).unwrap_or(0)
+        } else {
+            let index = items
+                .data
+                .partition_point(|row| entries.entry(row).timestamp < Some(timestamp));
+            if index > 0 && distance(index - 1) <= distance(index) {
+                index - 1
+            } else {
+                index.min(items.data.len() - 1)
+            }
+        };
+
+        self.table_view_state
+            .state
+            .select(Some(self.calculate_position_in_view_buffer()));
+    }
+
+    pub fn search_matcher(&self) -> anyhow::Result<Matcher> {
+        Matcher::new(self.search_input_text().value(), self.search_mode)
+    }
+
+    pub fn display_zone(&self) -> Option<Zone> {
+        self.display_zone
+    }
+
+    /// Switches the date column between the dates as written, local time and UTC.
+    pub fn cycle_display_zone(&mut self) {
+        self.display_zone = match self.display_zone {
+            None => Some(Zone::Local),
+            Some(Zone::Local) => Some(Zone::Utc),
+            Some(Zone::Utc) | Some(Zone::Fixed(_)) => None,
+        };
+
+        // Dates in filter queries are read in the display zone
+        if self.filter_mode == MatchMode::Query {
+            self.filter_by_current_input();
+        }
+    }
+
+    /// Zone set for the dates without a UTC offset of the selected tab, `None` when it uses the zone of the format.
+    pub fn selected_tab_zone(&self) -> Option<Zone> {
+        let tab = &self.tabs[self.selected_tab_index];
+        self.parser_settings.tab_zones.get(&tab.file_path).copied()
+    }
+
+    /// Switches the zone of the dates without a UTC offset of the selected tab between the zone of
+    /// the format, UTC and local time.
+    pub fn cycle_selected_tab_zone(&mut self) {
+        if matches!(self.tabs[self.selected_tab_index].tab_type, TabType::Combined) {
+            return;
+        }
+
+        self.cancel_jobs();
+        let tab = &self.tabs[self.selected_tab_index];
+        let tab_zones = &mut self.parser_settings.tab_zones;
+        match tab_zones.get(&tab.file_path) {
+            None => tab_zones.insert(tab.file_path.clone(), Zone::Utc),
+            Some(Zone::Utc) => tab_zones.insert(tab.file_path.clone(), Zone::Local),
+            Some(Zone::Local) | Some(Zone::Fixed(_)) => tab_zones.remove(&tab.file_path),
+        };
+
+        let entries = self.entries.get_mut(tab.id);
+        parser::resolve_timestamps(entries, &tab.file_path, &self.parser_settings);
+        self.filter_by_current_input();
+    }
+
+    /// Tab the selected entry comes from (another one than the selected tab in the combined tab).
+    fn selected_entry_tab_index(&self) -> Option<usize> {
+        let items = &self.tabs.get(self.selected_tab_index)?.filtered_view_items;
+        let row = items.data.get(items.selected_item_index)?;
+        self.tabs.iter().position(|tab| tab.id == row.tab_id)
+    }
+
+    fn selected_entry_timestamp(&self) -> Option<i64> {
+        let items = &self.tabs.get(self.selected_tab_index)?.filtered_view_items;
+        self.entries.entry(items.data.get(items.selected_item_index)?).timestamp
+    }
+
+    /// Clock offset in nanoseconds of the tab the selected entry comes from.
+    pub fn selected_clock_offset(&self) -> i64 {
+        self.selected_entry_tab_index()
+            .and_then(|tab_index| {
+                self.parser_settings
+                    .clock_offsets
+                    .get(&self.tabs[tab_index].file_path)
+                    .copied()
+            })
+            .unwrap_or_default()
+    }
+
+    fn set_clock_offset(&mut self, tab_index: usize, clock_offset: i64) {
+        self.cancel_jobs();
+        let tab = &self.tabs[tab_index];
+        let file_path = tab.file_path.clone();
+        if clock_offset == 0 {
+            self.parser_settings.clock_offsets.remove(&file_path);
+        } else {
+            self.parser_settings
+                .clock_offsets
+                .insert(file_path.clone(), clock_offset);
+        }
+
+        let entries = self.entries.get_mut(tab.id);
+        parser::resolve_timestamps(entries, &file_path, &self.parser_settings);
+        self.filter_by_current_input();
+    }
+
+    /// Moves the dates of the tab the selected entry comes from by the given number of nanoseconds.
+    pub fn shift_selected_clock(&mut self, nanoseconds: i64) {
+        if let Some(tab_index) = self.selected_entry_tab_index() {
+            self.set_clock_offset(tab_index, self.selected_clock_offset() + nanoseconds);
+        }
+    }
+
+    pub fn reset_selected_clock(&mut self) {
+        if let Some(tab_index) = self.selected_entry_tab_index() {
+            self.set_clock_offset(tab_index, 0);
+        }
+    }
+
+    pub fn clock_sync_mark(&self) -> &Option<(usize, i64)> {
+        &self.clock_sync_mark
+    }
+
+    /// Marks the selected entry as the one [`Self::sync_selected_clock`] lines entries of other tabs up with.
+    pub fn mark_selected_entry(&mut self) {
+        self.clock_sync_mark = self
+            .selected_entry_tab_index()
+            .zip(self.selected_entry_timestamp())
+            .map(|(tab_index, timestamp)| (self.tabs[tab_index].id, timestamp));
+    }
+
+    /// Sets the clock offset of the tab the selected entry comes from so that the entry happened
+    /// at the same moment as the marked one.
+    pub fn sync_selected_clock(&mut self) {
+        let (Some((mark_tab_id, mark_timestamp)), Some(tab_index), Some(timestamp)) = (
+            self.clock_sync_mark,
+            self.selected_entry_tab_index(),
+            self.selected_entry_timestamp(),
+        ) else {
+            return;
+        };
+
+        // Entries of the same file can't be out of sync with each other
+        if self.tabs[tab_index].id == mark_tab_id {
+            return;
+        }
+
+        self.clock_sync_mark = None;
+        self.set_clock_offset(
+            tab_index,
+            self.selected_clock_offset() + mark_timestamp - timestamp,
+        );
     }
 
     pub fn reload_combined_tab(&mut self) {
         let tabs = &mut self.tabs;
+        let entries = &self.entries;
+        let combined_items = &tabs[COMBINED_TAB_INDEX].filtered_view_items;
+        let selected_row = combined_items.data.get(combined_items.selected_item_index).copied();
+        // Rows of the same level are in date order, like when not sorting by level
+        let order_key = |row: &ViewRow| {
+            let severity = self.sort_by_level.then(|| Reverse(entries.entry(row).severity));
+            (severity, row_order_key(entries, row))
+        };
 
-        let mut all_tab_items = vec![];
-        for tab in tabs.iter() {
-            if matches!(tab.tab_type, TabType::Combined) {
-                continue;
+        // Each file is in date order already (or sorted again below)
+        let tab_rows = tabs
+            .iter()
+            .filter(|tab| !matches!(tab.tab_type, TabType::Combined))
+            .map(|tab| tab.filtered_view_items.data.as_slice())
+            .collect::<Vec<&[ViewRow]>>();
+        let mut all_tab_rows = merge_rows(entries, &tab_rows);
+
+        // The combined tab has its own filter on top of the ones of the tabs when the inputs aren't linked
+        let combined_filter = tabs[COMBINED_TAB_INDEX].filter_input_text.value();
+        if !self.inputs_linked && !combined_filter.trim().is_empty() {
+            let date_zone = self.display_zone.unwrap_or(Zone::Local);
+            if let Ok(query) = Query::new(combined_filter, self.filter_mode, date_zone) {
+                all_tab_rows.retain(|row| query.matches(entries.entry(row)));
             }
-
-            let mut current_tab_items = tab.filtered_view_items.data.clone();
-            all_tab_items.append(&mut current_tab_items);
+        }
+        if self.sort_by_level {
+            all_tab_rows.sort_by_key(order_key);
         }
 
-        all_tab_items.sort_by(|a, b| {
-            a[LogEntryIndices::Date as usize].cmp(&b[LogEntryIndices::Date as usize])
-        });
-
-        tabs[COMBINED_TAB_INDEX].filtered_view_items.data = all_tab_items;
-
-        tabs[COMBINED_TAB_INDEX]
-            .filtered_view_items
-            .selected_item_index = tabs[COMBINED_TAB_INDEX].filtered_view_items.data.len() - 1;
-
-        let items = tabs[COMBINED_TAB_INDEX].filtered_view_items.clone();
-        *tabs[COMBINED_TAB_INDEX].items_mut() = items;
+        let mut level_counts = HashMap::new();
+        for tab in tabs.iter().filter(|tab| !matches!(tab.tab_type, TabType::Combined)) {
+            for (level, count) in &tab.level_counts {
+                *level_counts.entry(*level).or_default() += count;
+            }
+        }
+        tabs[COMBINED_TAB_INDEX].level_counts = level_counts;
+
+        let items = &mut tabs[COMBINED_TAB_INDEX].filtered_view_items;
+        items.data = all_tab_rows.into();
+        self.views_version += 1;
+
+        // Selects the row selected before (or the next one left), rows being in the order of their key
+        let last_index = items.data.len().saturating_sub(1);
+        items.selected_item_index = match selected_row {
+            _ if self.tail_enabled => last_index,
+            Some(selected_row) => {
+                let selected_key = order_key(&selected_row);
+                items.data.partition_point(|row| order_key(row) < selected_key).min(last_index)
+            }
+            None => 0,
+        };
     }
 
     pub fn get_view_buffer_range(&self) -> Range<usize> {
@@ -343,29 +1778,24 @@ impl App {
             &mut self.tabs[self.selected_tab_index].filtered_view_items,
         );
 
-        let new_index = if search.is_none() || search.as_ref().unwrap().is_empty() {
-            std::cmp::min(index.saturating_add(1), items.data.len() - 1)
-        } else {
-            // search mode
-            let keywords = search
-                .as_ref()
-                .unwrap()
-                .split(',')
-                .map(|keyword| keyword.to_owned())
-                .collect::<Vec<String>>();
+        let matcher = match search.filter(|search| !search.is_empty()) {
+            // Invalid regexes are shown in the search input so there's nothing to look for
+            Some(search) => match Matcher::new(&search, self.search_mode) {
+                Ok(matcher) => Some(matcher),
+                Err(_) => return,
+            },
+            None => None,
+        };
 
+        let new_index = if let Some(matcher) = matcher {
+            // search mode
             let mut final_index = index;
-            'outer: loop {
+            loop {
                 index = std::cmp::min(index.saturating_add(1), items.data.len() - 1);
 
-                for search_keyword in &keywords {
-                    if items.data[index][LogEntryIndices::Log as usize]
-                        .to_lowercase()
-                        .contains(&search_keyword.to_lowercase())
-                    {
-                        final_index = index;
-                        break 'outer;
-                    }
+                if matcher.is_match(&self.entries.entry(&items.data[index])[LogEntryIndices::Log as usize]) {
+                    final_index = index;
+                    break;
                 }
 
                 if index == items.data.len() - 1 {
@@ -375,6 +1805,8 @@ impl App {
             }
 
             final_index
+        } else {
+            std::cmp::min(index.saturating_add(1), items.data.len() - 1)
         };
 
         items.selected_item_index = new_index;
@@ -401,28 +1833,24 @@ impl App {
             &mut self.tabs[self.selected_tab_index].filtered_view_items,
         );
 
-        let new_index = if search.is_none() || search.as_ref().unwrap().is_empty() {
-            std::cmp::max(index.saturating_sub(1), 0)
-        } else {
-            let keywords = search
-                .as_ref()
-                .unwrap()
-                .split(',')
-                .map(|keyword| keyword.to_owned())
-                .collect::<Vec<String>>();
+        let matcher = match search.filter(|search| !search.is_empty()) {
+            // Invalid regexes are shown in the search input so there's nothing to look for
+            Some(search) => match Matcher::new(&search, self.search_mode) {
+                Ok(matcher) => Some(matcher),
+                Err(_) => return,
+            },
+            None => None,
+        };
+
+        let new_index = if let Some(matcher) = matcher {
             // search mode
             let mut final_index = index;
-            'outer: loop {
+            loop {
                 index = std::cmp::max(index.saturating_sub(1), 0);
 
-                for search_keyword in &keywords {
-                    if items.data[index][LogEntryIndices::Log as usize]
-                        .to_lowercase()
-                        .contains(&search_keyword.to_lowercase())
-                    {
-                        final_index = index;
-                        break 'outer;
-                    }
+                if matcher.is_match(&self.entries.entry(&items.data[index])[LogEntryIndices::Log as usize]) {
+                    final_index = index;
+                    break;
                 }
 
                 if index == 0 {
@@ -432,6 +1860,8 @@ impl App {
             }
 
             final_index
+        } else {
+            std::cmp::max(index.saturating_sub(1), 0)
         };
 
         items.selected_item_index = new_index;
@@ -536,6 +1966,66 @@ impl App {
             .select(Some(self.calculate_position_in_view_buffer()));
     }
 
+    /// Indices of the entries that start a session, the first entry of each file counts as one too.
+    fn session_boundaries(&self) -> Vec<usize> {
+        let mut seen_sessions = HashSet::new();
+        self.tabs[self.selected_tab_index]
+            .filtered_view_items
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| seen_sessions.insert((row.tab_id, self.entries.entry(row).session)))
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>()
+    }
+
+    pub fn next_session(&mut self) {
+        if self.tabs.is_empty() {
+            return;
+        }
+
+        let selected_item_index = self.tabs[self.selected_tab_index]
+            .filtered_view_items
+            .selected_item_index;
+        if let Some(index) = self
+            .session_boundaries()
+            .into_iter()
+            .find(|index| *index > selected_item_index)
+        {
+            self.tabs[self.selected_tab_index]
+                .filtered_view_items
+                .selected_item_index = index;
+        }
+
+        self.table_view_state
+            .state
+            .select(Some(self.calculate_position_in_view_buffer()));
+    }
+
+    pub fn previous_session(&mut self) {
+        if self.tabs.is_empty() {
+            return;
+        }
+
+        let selected_item_index = self.tabs[self.selected_tab_index]
+            .filtered_view_items
+            .selected_item_index;
+        if let Some(index) = self
+            .session_boundaries()
+            .into_iter()
+            .rev()
+            .find(|index| *index < selected_item_index)
+        {
+            self.tabs[self.selected_tab_index]
+                .filtered_view_items
+                .selected_item_index = index;
+        }
+
+        self.table_view_state
+            .state
+            .select(Some(self.calculate_position_in_view_buffer()));
+    }
+
     pub fn switch_to_item_view(&mut self) {
         if self.tabs.is_empty()
             || self.tabs[self.selected_tab_index]
@@ -546,7 +2036,13 @@ impl App {
             return;
         }
 
-        if !self.filter_input_text.value().is_empty() {
+        let items = &self.tabs[self.selected_tab_index].filtered_view_items;
+        if let RowKind::Separator { .. } = items.data[items.selected_item_index].kind {
+            self.expand_selected_separator();
+            return;
+        }
+
+        if !self.filter_input_text().value().is_empty() {
             self.view_mode.push_back(ViewMode::TableItem(
                 self.tabs[self.selected_tab_index]
                     .filtered_view_items
@@ -572,13 +2068,9 @@ impl App {
         if let Some(files) = files {
             for file in files {
                 let file_path = file.to_str().unwrap().to_string();
-                let table_items = TableItems {
-                    data: parser::parse_log_by_path(&file_path).unwrap_or_default(),
-                    selected_item_index: 0,
-                };
-                self.tabs
-                    .push(Tab::new(file_path, table_items, TabType::Normal));
-                self.selected_tab_index = self.tabs.len() - 1;
+                let entries =
+                    parser::parse_log_by_path(&file_path, &self.parser_settings).unwrap_or_default();
+                self.add_tab(file_path, entries);
             }
             self.reload_combined_tab();
         }
@@ -609,73 +2101,419 @@ impl App {
             .select(Some(self.calculate_position_in_view_buffer()));
     }
 
-    pub fn filter_by_current_input(&mut self, filter: String) {
-        for tab in &mut self.tabs {
-            tab.filtered_view_items.data = tab
-                .items()
-                .data
+    /// Filters every tab with its filter input (or the shared one when the inputs are linked) and the filter rules.
+    ///
+    /// The filtering runs on a worker thread (see [`Self::update_jobs`]), cancelling the one still running.
+    pub fn filter_by_current_input(&mut self) {
+        let (settings, valid_rules) = self.filter_settings();
+        // Invalid rules are skipped (and shown without a match count)
+        for (rule, valid) in self.filter_rules.iter_mut().zip(valid_rules) {
+            rule.match_count = valid.then_some(0);
+        }
+
+        let mut tab_filters = vec![];
+        let mut selected_lines = HashMap::new();
+        let mut selected_indices = HashMap::new();
+        for (tab_index, tab) in self.tabs.iter().enumerate() {
+            // The combined tab is rebuilt from the other tabs
+            if matches!(tab.tab_type, TabType::Combined) {
+                continue;
+            }
+
+            // Invalid queries and regexes keep the current view, the error is shown in the filter input
+            let Ok(query) = self.tab_query(tab) else {
+                continue;
+            };
+
+            let items = &tab.filtered_view_items;
+            selected_lines.insert(
+                tab_index,
+                items
+                    .data
+                    .get(items.selected_item_index)
+                    .map(|row| self.entries.entry(row).line),
+            );
+            selected_indices.insert(tab_index, items.selected_item_index);
+            tab_filters.push(TabFilter {
+                tab_index,
+                tab_id: tab.id,
+                entries: self.entries.shared(tab.id),
+                query,
+                expanded_lines: tab.expanded_lines.clone(),
+            });
+        }
+
+        let total = tab_filters.iter().map(|tab_filter| tab_filter.entries.len()).sum();
+        let pending_tabs = tab_filters.len();
+        // Replacing the job cancels the stale one
+        self.filter_job = Some(FilterJob {
+            job: Job::spawn(self.event_sender.clone(), move |context| {
+                run_filter_job(context, tab_filters, settings)
+            }),
+            selected_lines,
+            selected_indices,
+            pending_tabs,
+            checked: 0,
+            total,
+        });
+        self.update_jobs();
+    }
+
+    /// Filters applied to every tab, along with whether each filter rule is valid.
+    fn filter_settings(&self) -> (FilterSettings, Vec<bool>) {
+        let date_zone = self.display_zone.unwrap_or(Zone::Local);
+        let (rules, valid_rules) = self
+            .filter_rules
+            .iter()
+            .map(|rule| {
+                let rule_query = Query::new(&rule.query, MatchMode::Query, date_zone).ok();
+                let valid = rule_query.is_some();
+                ((rule_query.filter(|_| rule.enabled), rule.exclude), valid)
+            })
+            .unzip();
+        let settings = FilterSettings {
+            rules,
+            hidden_levels: self.hidden_levels.clone(),
+            latest_session_only: self.latest_session_only,
+            time_range: self.time_range,
+            context_lines: self.context_lines,
+            sort_by_level: self.sort_by_level,
+        };
+
+        (settings, valid_rules)
+    }
+
+    /// Filter query of a tab (the shared one when the inputs are linked), `None` when the filter is empty.
+    fn tab_query(&self, tab: &Tab) -> anyhow::Result<Option<Query>> {
+        let filter = if self.inputs_linked {
+            self.filter_input_text.value()
+        } else {
+            tab.filter_input_text.value()
+        };
+        let query = Query::new(filter, self.filter_mode, self.display_zone.unwrap_or(Zone::Local))?;
+
+        Ok((!filter.trim().is_empty()).then_some(query))
+    }
+
+    /// Replaces the entries of the tabs whose file changed. When entries were only added at the end of
+    /// the file (the usual case when tailing), only those are filtered and merged into the combined tab.
+    pub fn update_tab_entries(&mut self, updates: Vec<(usize, Vec<LogEntry>)>) {
+        // Context rows around the new entries could change the rows before them, new entries don't go
+        // at the end of views sorted by level, and a running job would miss the new entries
+        let mut refilter = self.filter_job.is_some() || self.context_lines > 0 || self.sort_by_level;
+        let mut appended_rows = vec![];
+        for (tab_id, entries) in updates {
+            let Some(tab_index) = self.tabs.iter().position(|tab| tab.id == tab_id) else {
+                continue;
+            };
+
+            let first_new_index = self.entries.get(tab_id).len();
+            let appended = is_append(self.entries.get(tab_id), &entries);
+            if !appended {
+                // The file was truncated or replaced, so the rows of its old entries can point past the new ones
+                self.tabs[tab_index].filtered_view_items = TableItems::default();
+                self.drop_rows_of_tab(tab_id);
+            }
+            self.entries.insert(tab_id, entries);
+            if refilter || !appended || !self.tabs[tab_index].expanded_lines.is_empty() {
+                refilter = true;
+                continue;
+            }
+
+            match self.filter_appended_entries(tab_index, first_new_index) {
+                Some(rows) => appended_rows.push(rows),
+                None => refilter = true,
+            }
+        }
+
+        if refilter {
+            self.filter_by_current_input();
+        } else {
+            self.merge_into_combined_tab(appended_rows);
+        }
+    }
+
+    /// Adds the entries of a tab from `first_new_index` on that the filters keep to its view, returning their
+    /// rows, or `None` when they can change which of the other entries are kept (a new session started).
+    fn filter_appended_entries(&mut self, tab_index: usize, first_new_index: usize) -> Option<Vec<ViewRow>> {
+        let tab = &self.tabs[tab_index];
+        // Invalid queries and regexes keep the current view, the error is shown in the filter input
+        let Ok(query) = self.tab_query(tab) else {
+            return Some(vec![]);
+        };
+
+        let (settings, _) = self.filter_settings();
+        let entries = self.entries.get(tab.id);
+        let latest_session = entries.iter().map(|item| item.session).max().unwrap_or_default();
+        let previous_latest_session = entries[..first_new_index]
+            .iter()
+            .map(|item| item.session)
+            .max()
+            .unwrap_or_default();
+        if settings.latest_session_only && latest_session != previous_latest_session {
+            return None;
+        }
+
+        let mut level_counts = HashMap::new();
+        let mut rule_match_counts = vec![0; settings.rules.len()];
+        let rows = entries[first_new_index..]
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| settings.shows(item, latest_session))
+            .filter(|(_, item)| {
+                let verdict =
+                    settings.keeps(item, query.as_ref(), false, &mut rule_match_counts, &mut level_counts);
+                verdict == Verdict::Match
+            })
+            .map(|(offset, _)| ViewRow {
+                tab_id: tab.id,
+                index: first_new_index + offset,
+                kind: RowKind::Match,
+            })
+            .collect::<Vec<ViewRow>>();
+
+        for (rule, count) in self.filter_rules.iter_mut().zip(rule_match_counts) {
+            rule.match_count = rule.match_count.map(|match_count| match_count + count);
+        }
+        let tab = &mut self.tabs[tab_index];
+        for (level, count) in level_counts {
+            *tab.level_counts.entry(level).or_default() += count;
+        }
+        let items = &mut tab.filtered_view_items;
+        Arc::make_mut(&mut items.data).extend(&rows);
+        if self.tail_enabled {
+            items.selected_item_index = items.data.len().saturating_sub(1);
+        }
+        self.views_version += 1;
+
+        Some(rows)
+    }
+
+    /// Merges the rows added to the tabs into the combined tab, only moving the rows of the combined tab
+    /// that come after the last row of the same tab (dates can go back within a file, so they'd be put
+    /// elsewhere by the first date of the rows).
+    fn merge_into_combined_tab(&mut self, mut tab_rows: Vec<Vec<ViewRow>>) {
+        let entries = &self.entries;
+        let combined_tab = &self.tabs[COMBINED_TAB_INDEX];
+        if !self.inputs_linked {
+            if let Ok(Some(query)) = self.tab_query(combined_tab) {
+                for rows in &mut tab_rows {
+                    rows.retain(|row| query.matches(entries.entry(row)));
+                }
+            }
+        }
+
+        let mut level_counts = HashMap::new();
+        for tab in self.tabs.iter().filter(|tab| !matches!(tab.tab_type, TabType::Combined)) {
+            for (level, count) in &tab.level_counts {
+                *level_counts.entry(*level).or_default() += count;
+            }
+        }
+
+        let combined_tab = &mut self.tabs[COMBINED_TAB_INDEX];
+        combined_tab.level_counts = level_counts;
+        let items = &mut combined_tab.filtered_view_items;
+        let selected_row = items.data.get(items.selected_item_index).copied();
+        for rows in tab_rows.iter().filter(|rows| !rows.is_empty()) {
+            let data = Arc::make_mut(&mut items.data);
+            let tab_id = rows[0].tab_id;
+            let start = data.iter().rposition(|row| row.tab_id == tab_id).map_or(0, |position| position + 1);
+            let later_rows = data.split_off(start);
+            data.extend(merge_rows(entries, &[&later_rows, rows]));
+            self.views_version += 1;
+        }
+        if self.tail_enabled {
+            items.selected_item_index = items.data.len().saturating_sub(1);
+        } else if let Some(selected_row) = selected_row {
+            // Rows were only added, some of them maybe before the selected one
+            let moved_by = items.data[items.selected_item_index..]
                 .iter()
-                .filter(|item| {
-                    if filter.trim().is_empty() {
-                        return true;
-                    }
-                    let keywords = filter
-                        .split(',')
-                        .map(|keyword| keyword.to_owned())
-                        .collect::<Vec<String>>();
-
-                    let mut include_item = false;
-                    for filter_keyword in &keywords {
-                        include_item = include_item
-                            || item[LogEntryIndices::Log as usize]
-                                .to_lowercase()
-                                .contains(filter_keyword.to_lowercase().as_str());
-
-                        if include_item {
-                            break;
-                        }
+                .position(|row| *row == selected_row)
+                .unwrap_or_default();
+            items.selected_item_index += moved_by;
+        }
+    }
+
+    /// Shows what the filter and search jobs found since the last call.
+    pub fn update_jobs(&mut self) {
+        if let Some(filter_job) = &self.filter_job {
+            for result in filter_job.job.results() {
+                self.apply_filter_result(result);
+            }
+        }
+        if self.filter_job.as_ref().is_some_and(|filter_job| filter_job.pending_tabs == 0) {
+            self.filter_job = None;
+            self.reload_combined_tab();
+        }
+
+        if let Some(search_job) = &self.search_job {
+            for result in search_job.results() {
+                let Some(results) = &mut self.search_results else {
+                    continue;
+                };
+
+                match result {
+                    SearchResult::Matches(matches, checked) => {
+                        results.matches.extend(matches);
+                        results.checked += checked;
                     }
+                    SearchResult::Done => results.complete = true,
+                }
+            }
+        }
+        if self.search_results.as_ref().is_some_and(|results| results.complete) {
+            self.search_job = None;
+        }
+    }
 
-                    include_item
-                })
-                .cloned()
-                .collect::<Vec<Vec<String>>>();
+    fn apply_filter_result(&mut self, result: FilterResult) {
+        let Some(filter_job) = &mut self.filter_job else {
+            return;
+        };
 
-            tab.filtered_view_items.selected_item_index = if self.tail_enabled {
-                tab.filtered_view_items.data.len() - 1
-            } else {
-                0
-            };
+        match result {
+            FilterResult::Checked(checked) => filter_job.checked += checked,
+            FilterResult::Matches {
+                tab_index,
+                matches_before,
+                rows,
+            } => {
+                let Some(tab) = self.tabs.get_mut(tab_index) else {
+                    return;
+                };
+
+                // The first matches replace the view without copying it (a search job may share it)
+                let items = &mut tab.filtered_view_items;
+                let entries = self.entries.get(tab.id);
+                filter_job.keep_moved_selection(tab_index, items, entries);
+                if matches_before == 0 {
+                    items.data = rows.into();
+                } else {
+                    let data = Arc::make_mut(&mut items.data);
+                    data.truncate(matches_before);
+                    data.extend(rows);
+                }
+                // The search waits for the job to finish (see `update_search_results`), so the version isn't bumped
+                filter_job.restore_selection(tab_index, items, entries, self.sort_by_level, self.tail_enabled);
+            }
+            FilterResult::Done {
+                tab_index,
+                match_count,
+                view,
+                level_counts,
+                rule_match_counts,
+            } => {
+                filter_job.pending_tabs = filter_job.pending_tabs.saturating_sub(1);
+                for (rule, count) in self.filter_rules.iter_mut().zip(rule_match_counts) {
+                    rule.match_count = rule.match_count.map(|match_count| match_count + count);
+                }
+                let Some(tab) = self.tabs.get_mut(tab_index) else {
+                    return;
+                };
+
+                let items = &mut tab.filtered_view_items;
+                let entries = self.entries.get(tab.id);
+                filter_job.keep_moved_selection(tab_index, items, entries);
+                match view {
+                    Some(view) => items.data = view.into(),
+                    None if items.data.len() == match_count => {}
+                    None if match_count == 0 => items.data = Arc::default(),
+                    None => Arc::make_mut(&mut items.data).truncate(match_count),
+                }
+                tab.level_counts = level_counts;
+                filter_job.restore_selection(tab_index, items, entries, self.sort_by_level, self.tail_enabled);
+                self.views_version += 1;
+            }
         }
+    }
+
+    /// How far the running filter job got, between 0 and 1.
+    pub fn filter_progress(&self) -> Option<f64> {
+        self.filter_job
+            .as_ref()
+            .map(|filter_job| filter_job.checked as f64 / filter_job.total.max(1) as f64)
+    }
+
+    pub fn sort_by_level(&self) -> bool {
+        self.sort_by_level
+    }
 
-        self.reload_combined_tab();
+    /// Lists the most severe entries first (or the entries in date order again).
+    pub fn toggle_sort_by_level(&mut self) {
+        self.sort_by_level = !self.sort_by_level;
+        self.filter_by_current_input();
     }
 
     pub fn selected_log_entry_in_text(&self) -> String {
         let items = &self.tabs()[self.selected_tab_index()].filtered_view_items;
-
-        let date = &items.data[items.selected_item_index][LogEntryIndices::Date as usize];
-        let level = &items.data[items.selected_item_index][LogEntryIndices::Level as usize];
-        let text = &items.data[items.selected_item_index][LogEntryIndices::Log as usize];
-        let log_entry = format!("{:<25}{:<8}{}", date, level, text);
+        let entry = self.entries.entry(&items.data[items.selected_item_index]);
+
+        let date = &entry[LogEntryIndices::Date as usize];
+        let level = &entry[LogEntryIndices::Level as usize];
+        let text = &entry[LogEntryIndices::Log as usize];
+        let mut log_entry = format!("{:<25}{:<8}{}", date, level, text);
+
+        let fields = &entry.fields;
+        if !fields.is_empty() {
+            log_entry += "\n";
+            for (name, value) in fields {
+                log_entry += &format!("\n{:<12}{}", name, value);
+            }
+        }
 
         log_entry
     }
 
+    /// Filter input of the selected tab, or the one shared by every tab when the inputs are linked.
     pub fn filter_input_text(&self) -> &Input {
-        &self.filter_input_text
+        match self.tabs.get(self.selected_tab_index) {
+            Some(tab) if !self.inputs_linked => &tab.filter_input_text,
+            _ => &self.filter_input_text,
+        }
     }
 
     pub fn filter_input_text_mut(&mut self) -> &mut Input {
-        &mut self.filter_input_text
+        match self.tabs.get_mut(self.selected_tab_index) {
+            Some(tab) if !self.inputs_linked => &mut tab.filter_input_text,
+            _ => &mut self.filter_input_text,
+        }
     }
 
+    /// Search input of the selected tab, or the one shared by every tab when the inputs are linked.
     pub fn search_input_text(&self) -> &Input {
-        &self.search_input_text
+        match self.tabs.get(self.selected_tab_index) {
+            Some(tab) if !self.inputs_linked => &tab.search_input_text,
+            _ => &self.search_input_text,
+        }
     }
 
     pub fn search_input_text_mut(&mut self) -> &mut Input {
-        &mut self.search_input_text
+        match self.tabs.get_mut(self.selected_tab_index) {
+            Some(tab) if !self.inputs_linked => &mut tab.search_input_text,
+            _ => &mut self.search_input_text,
+        }
+    }
+
+    pub fn inputs_linked(&self) -> bool {
+        self.inputs_linked
+    }
+
+    /// Switches between one filter and search shared by every tab and a filter and search for each tab.
+    pub fn toggle_inputs_linked(&mut self) {
+        if self.inputs_linked {
+            // Every tab starts from the shared inputs
+            for tab in &mut self.tabs {
+                tab.filter_input_text = Input::new(self.filter_input_text.to_string());
+                tab.search_input_text = Input::new(self.search_input_text.to_string());
+            }
+        } else if let Some(tab) = self.tabs.get(self.selected_tab_index) {
+            // The inputs of the selected tab drive every tab again
+            self.filter_input_text = Input::new(tab.filter_input_text.to_string());
+            self.search_input_text = Input::new(tab.search_input_text.to_string());
+        }
+
+        self.inputs_linked = !self.inputs_linked;
+        self.filter_by_current_input();
     }
 
     pub fn mouse_position_mut(&mut self) -> &mut (u16, u16) {
@@ -748,22 +2586,396 @@ impl Drop for App {
             .map(|tab|{
             tab.file_path.replace("\\\\", "\\").clone()
             }).collect::<Vec<String>>(),
-            "search_input_text": self.search_input_text().to_string(),
-            "filter_input_text": self.filter_input_text().to_string(),
+            "search_input_text": self.search_input_text.to_string(),
+            "filter_input_text": self.filter_input_text.to_string(),
+            "inputs_linked": self.inputs_linked(),
+            "context_lines": self.context_lines(),
+            "hidden_levels": self.hidden_levels.iter().map(|level| match level {
+                Some(severity) => format!("{:?}", severity),
+                None => "none".to_owned(),
+            }).collect::<Vec<String>>(),
+            "tab_inputs": self.tabs().iter()
+            .filter(|tab| !tab.file_path.is_empty())
+            .map(|tab| {
+                (tab.file_path.clone(), json!({
+                    "filter": tab.filter_input_text.to_string(),
+                    "search": tab.search_input_text.to_string(),
+                }))
+            }).collect::<serde_json::Map<String, Value>>(),
             "tail": self.tail_enabled(),
+            "sort_by_level": self.sort_by_level(),
+            "filter_mode": self.filter_mode().to_string(),
+            "search_mode": self.search_mode().to_string(),
+            "infer_levels": self.infer_levels(),
+            "level_rules": self.parser_settings().level_rules.iter().map(|(format_name, rules)| {
+                (format_name.clone(), Value::Array(rules.iter().map(|rule| json!({
+                    "pattern": rule.pattern.as_str(),
+                    "level": format!("{:?}", rule.severity),
+                })).collect::<Vec<Value>>()))
+            }).collect::<serde_json::Map<String, Value>>(),
+            "format_zones": self.parser_settings().format_zones.iter().map(|(format_name, zone)| {
+                (format_name.clone(), Value::String(zone.to_string()))
+            }).collect::<serde_json::Map<String, Value>>(),
+            "tab_zones": self.parser_settings().tab_zones.iter().map(|(file_path, zone)| {
+                (file_path.clone(), Value::String(zone.to_string()))
+            }).collect::<serde_json::Map<String, Value>>(),
+            "display_zone": self.display_zone().map(|zone| zone.to_string()),
+            "filter_rules": self.filter_rules().iter().map(|rule| json!({
+                "query": rule.query,
+                "exclude": rule.exclude,
+                "enabled": rule.enabled,
+            })).collect::<Vec<Value>>(),
+            "clock_offsets": self.parser_settings().clock_offsets.iter().map(|(file_path, clock_offset)| {
+                (file_path.clone(), json!(*clock_offset as f64 / 1_000_000.0))
+            }).collect::<serde_json::Map<String, Value>>(),
         });
-        let mut config_file = std::fs::File::create(format!(
+        let config_file = std::fs::File::create(format!(
             "{}/{}/{}",
             std::env::var("LOCALAPPDATA").unwrap(),
             CONFIGS_PATH,
             CONFIG_FILE_NAME
-        ))
-        .unwrap();
+        ));
 
         println!("Serializing config ..");
-        config_file
-            .write_all(serialized.to_string().as_bytes())
-            .unwrap();
+        // Panicking while dropping the app would hide what made it close
+        let saved = config_file
+            .and_then(|mut config_file| config_file.write_all(serialized.to_string().as_bytes()));
+        if let Err(e) = saved {
+            println!("Could not save the config: {}", e);
+            return;
+        }
         println!("DONE");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(tab_id: usize, indices: &[usize]) -> Vec<ViewRow> {
+        indices
+            .iter()
+            .map(|index| ViewRow {
+                tab_id,
+                index: *index,
+                kind: RowKind::Match,
+            })
+            .collect()
+    }
+
+    fn indices(rows: &[ViewRow]) -> Vec<usize> {
+        rows.iter().map(|row| row.index).collect()
+    }
+
+    fn entry(line: usize, severity: Option<Severity>, log: &str) -> LogEntry {
+        LogEntry {
+            columns: vec![
+                "app.log".to_string(),
+                "1".to_string(),
+                "".to_string(),
+                "".to_string(),
+                log.to_string(),
+            ],
+            severity,
+            line,
+            ..Default::default()
+        }
+    }
+
+    /// App without tabs, which neither reads nor saves a config file.
+    fn test_app() -> App {
+        // The folder of the config file doesn't exist
+        std::env::set_var("LOCALAPPDATA", std::env::temp_dir().join("log-viewer-rs-tests"));
+        let (event_sender, _) = std::sync::mpsc::channel();
+        App::new(vec![], event_sender)
+    }
+
+    fn dated_entries(dates: &[i64]) -> Vec<LogEntry> {
+        dates
+            .iter()
+            .enumerate()
+            .map(|(index, date)| LogEntry {
+                timestamp: Some(*date),
+                order_timestamp: Some(*date),
+                ..entry(index + 1, None, "")
+            })
+            .collect()
+    }
+
+    fn finish_jobs(app: &mut App) {
+        while app.filter_job.is_some() || app.search_job.is_some() {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update_jobs();
+        }
+    }
+
+    #[test]
+    fn test_close_tab_while_filtering() {
+        let mut app = test_app();
+        app.add_tab("first.log".to_owned(), dated_entries(&[1, 3, 5]));
+        app.add_tab("second.log".to_owned(), dated_entries(&[2, 4, 6, 8, 10]));
+        app.filter_by_current_input();
+        finish_jobs(&mut app);
+        assert_eq!(app.tabs[COMBINED_TAB_INDEX].filtered_view_items.data.len(), 8);
+
+        app.selected_tab_index = COMBINED_TAB_INDEX;
+        app.tabs[COMBINED_TAB_INDEX].filtered_view_items.selected_item_index = 7;
+        app.filter_by_current_input();
+        app.selected_tab_index = 2;
+        app.close_selected_tab();
+
+        // Every view only points at the entries left, before and after the filter job is done
+        for _ in 0..2 {
+            for tab in app.tabs() {
+                let items = &tab.filtered_view_items;
+                assert!(items.data.iter().all(|row| row.index < app.entries.get(row.tab_id).len()));
+                assert!(items.data.is_empty() || items.selected_item_index < items.data.len());
+            }
+            assert!(app.search_results.is_none());
+            finish_jobs(&mut app);
+        }
+        assert_eq!(indices(&app.tabs[COMBINED_TAB_INDEX].filtered_view_items.data), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_merge_rows() {
+        let mut entries = EntryStore::default();
+        entries.insert(0, dated_entries(&[1, 3, 3]));
+        entries.insert(1, dated_entries(&[3, 5]));
+        // A file without any full date
+        entries.insert(2, (1..=2).map(|line| entry(line, None, "")).collect());
+
+        // Separators are left out
+        let mut view = rows(0, &[0, 2]);
+        view.insert(1, ViewRow { tab_id: 0, index: 1, kind: RowKind::Separator { hidden: 1, last_line: 2 } });
+
+        let merged = merge_rows(&entries, &[&rows(2, &[0, 1]), &rows(1, &[0, 1]), &view]);
+        assert_eq!(
+            merged.iter().map(|row| (row.tab_id, row.index)).collect::<Vec<_>>(),
+            [(0, 0), (0, 2), (1, 0), (1, 1), (2, 0), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_is_append() {
+        let old = dated_entries(&[1, 2]);
+        assert!(is_append(&[], &old));
+        assert!(is_append(&old, &dated_entries(&[1, 2, 3])));
+        assert!(!is_append(&old, &dated_entries(&[1])));
+
+        // The last entry gained continuation lines, so it may not be filtered the same anymore
+        let mut new = dated_entries(&[1, 2, 3]);
+        new[1].columns[LogEntryIndices::Log as usize] += "\n    at main";
+        assert!(!is_append(&old, &new));
+    }
+
+    #[test]
+    fn test_merge_appended_entries() {
+        let mut app = test_app();
+        app.add_tab("first.log".to_owned(), dated_entries(&[1, 3, 5]));
+        app.add_tab("second.log".to_owned(), dated_entries(&[2, 4, 6]));
+        app.filter_by_current_input();
+        finish_jobs(&mut app);
+
+        let tab_ids = [app.tabs[1].id, app.tabs[2].id];
+        app.update_tab_entries(vec![
+            (tab_ids[0], dated_entries(&[1, 3, 5, 6, 7])),
+            (tab_ids[1], dated_entries(&[2, 4, 6, 6, 9])),
+        ]);
+        assert!(app.filter_job.is_none());
+        let merged = app.tabs[COMBINED_TAB_INDEX].filtered_view_items.data.clone();
+        app.reload_combined_tab();
+        assert_eq!(merged, app.tabs[COMBINED_TAB_INDEX].filtered_view_items.data);
+        assert_eq!(merged.len(), 10);
+    }
+
+    #[test]
+    fn test_merge_appended_entries_going_back() {
+        let mut app = test_app();
+        app.add_tab("first.log".to_owned(), dated_entries(&[1, 5, 3]));
+        app.add_tab("second.log".to_owned(), dated_entries(&[2, 6]));
+        app.filter_by_current_input();
+        finish_jobs(&mut app);
+        app.tabs[COMBINED_TAB_INDEX].filtered_view_items.selected_item_index = 4;
+
+        // The clock of the first file went back, the new entry goes after its last one rather than by its date
+        let tab_id = app.tabs[1].id;
+        app.update_tab_entries(vec![(tab_id, dated_entries(&[1, 5, 3, 4]))]);
+        assert!(app.filter_job.is_none());
+        let items = app.tabs[COMBINED_TAB_INDEX].filtered_view_items.clone();
+        let selected_row = items.data[items.selected_item_index];
+        assert_eq!((selected_row.tab_id, selected_row.index), (app.tabs[2].id, 1));
+        app.reload_combined_tab();
+        assert_eq!(items.data, app.tabs[COMBINED_TAB_INDEX].filtered_view_items.data);
+    }
+
+    #[test]
+    fn test_update_truncated_tab() {
+        let mut app = test_app();
+        app.add_tab("app.log".to_owned(), dated_entries(&[1, 2, 3, 4]));
+        app.filter_by_current_input();
+        finish_jobs(&mut app);
+        app.tabs[1].filtered_view_items.selected_item_index = 3;
+
+        // The file was rotated, the views only point at the new entries until the filter job rebuilds them
+        let tab_id = app.tabs[1].id;
+        app.update_tab_entries(vec![(tab_id, dated_entries(&[5, 6]))]);
+        for _ in 0..2 {
+            for tab in app.tabs() {
+                assert!(tab.filtered_view_items.data.iter().all(|row| row.index < 2));
+            }
+            finish_jobs(&mut app);
+        }
+        assert_eq!(indices(&app.tabs[COMBINED_TAB_INDEX].filtered_view_items.data), [0, 1]);
+    }
+
+    #[test]
+    fn test_entries_with_context() {
+        let entries = (1..=8)
+            .map(|line| entry(line, None, ""))
+            .collect::<Vec<LogEntry>>();
+        let entries = entries.iter().enumerate().collect::<Vec<(usize, &LogEntry)>>();
+        let mut is_match = vec![false; entries.len()];
+        is_match[3] = true;
+
+        let rows = entries_with_context(0, &entries, &is_match, 1, &HashSet::from([8]));
+        assert_eq!(
+            rows.iter().map(|row| (row.index, row.kind)).collect::<Vec<_>>(),
+            [
+                (0, RowKind::Separator { hidden: 2, last_line: 2 }),
+                (2, RowKind::Context),
+                (3, RowKind::Match),
+                (4, RowKind::Context),
+                (5, RowKind::Separator { hidden: 2, last_line: 7 }),
+                (7, RowKind::Context),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_job_keeps_moved_selection() {
+        let (event_sender, _) = std::sync::mpsc::channel();
+        let mut filter_job = FilterJob {
+            job: Job::spawn(event_sender, |_| {}),
+            selected_lines: HashMap::from([(1, Some(6))]),
+            selected_indices: HashMap::from([(1, 5)]),
+            pending_tabs: 1,
+            checked: 0,
+            total: 10,
+        };
+        let entries = (1..=10)
+            .map(|line| entry(line, None, ""))
+            .collect::<Vec<LogEntry>>();
+        let mut items = TableItems::all(0, 10);
+        items.selected_item_index = 5;
+        let mut show = |items: &mut TableItems, rows: Vec<ViewRow>| {
+            filter_job.keep_moved_selection(1, items, &entries);
+            items.data = rows.into();
+            filter_job.restore_selection(1, items, &entries, false, false);
+        };
+
+        // The entry selected when the job started is selected again once it's in the view
+        show(&mut items, rows(0, &[0, 1, 2]));
+        assert_eq!(items.selected_item_index, 2);
+        show(&mut items, rows(0, &[0, 1, 2, 5, 7]));
+        assert_eq!(items.selected_item_index, 3);
+
+        // Unless the user selected another one in the meantime
+        items.selected_item_index = 1;
+        show(&mut items, rows(0, &[0, 1, 2, 5, 7, 8]));
+        assert_eq!(items.selected_item_index, 1);
+        show(&mut items, rows(0, &[1, 2, 5, 7, 8]));
+        assert_eq!(items.selected_item_index, 0);
+    }
+
+    #[test]
+    fn test_reload_keeps_combined_selection() {
+        let mut app = test_app();
+        app.add_tab("first.log".to_owned(), dated_entries(&[1, 3, 5]));
+        app.add_tab("second.log".to_owned(), dated_entries(&[2, 4, 6]));
+        app.filter_by_current_input();
+        finish_jobs(&mut app);
+        let tab_id = app.tabs[1].id;
+        app.tabs[COMBINED_TAB_INDEX].filtered_view_items.selected_item_index = 2;
+
+        let selected_row = |app: &App| {
+            let items = &app.tabs[COMBINED_TAB_INDEX].filtered_view_items;
+            (items.data[items.selected_item_index].tab_id, items.data[items.selected_item_index].index)
+        };
+        // The selected entry moved up a row
+        app.tabs[1].filtered_view_items.data = rows(tab_id, &[1, 2]).into();
+        app.reload_combined_tab();
+        assert_eq!(selected_row(&app), (tab_id, 1));
+
+        // The selected entry is hidden, so the next one left is selected
+        app.tabs[1].filtered_view_items.data = rows(tab_id, &[2]).into();
+        app.reload_combined_tab();
+        assert_eq!(selected_row(&app), (app.tabs[2].id, 1));
+    }
+
+    #[test]
+    fn test_context_candidates() {
+        let settings = FilterSettings {
+            rules: vec![(Query::new("noise", MatchMode::Query, Zone::Utc).ok(), true)],
+            hidden_levels: HashSet::from([Some(Severity::Debug)]),
+            latest_session_only: false,
+            time_range: None,
+            context_lines: 1,
+            sort_by_level: false,
+        };
+        let query = Query::new("failed", MatchMode::Query, Zone::Utc).ok();
+        let verdict = |item: &LogEntry, with_context: bool| {
+            settings.keeps(item, query.as_ref(), with_context, &mut [0], &mut HashMap::new())
+        };
+
+        let failed = entry(1, Some(Severity::Error), "Request failed");
+        let other = entry(2, Some(Severity::Info), "Retrying");
+        assert_eq!(verdict(&failed, true), Verdict::Match);
+        assert_eq!(verdict(&other, true), Verdict::Context);
+        assert_eq!(verdict(&other, false), Verdict::Hidden);
+        // Entries hidden by the rules and level toggles don't come back as context
+        assert_eq!(verdict(&entry(3, Some(Severity::Info), "noise"), true), Verdict::Hidden);
+        assert_eq!(verdict(&entry(4, Some(Severity::Debug), "Retrying"), true), Verdict::Hidden);
+    }
+
+    #[test]
+    fn test_combined_tab_sorted_by_level() {
+        let leveled_entries = |levels: &[(Severity, i64)]| {
+            let mut entries = dated_entries(&levels.iter().map(|(_, date)| *date).collect::<Vec<i64>>());
+            for (entry, (severity, _)) in entries.iter_mut().zip(levels) {
+                entry.severity = Some(*severity);
+            }
+            entries
+        };
+        let mut app = test_app();
+        app.add_tab("first.log".to_owned(), leveled_entries(&[(Severity::Info, 1), (Severity::Error, 10)]));
+        app.add_tab("second.log".to_owned(), leveled_entries(&[(Severity::Info, 2), (Severity::Error, 5)]));
+        app.toggle_sort_by_level();
+        finish_jobs(&mut app);
+
+        // Rows of the same level are in date order across the files too
+        let dates = app.tabs[COMBINED_TAB_INDEX]
+            .filtered_view_items
+            .data
+            .iter()
+            .map(|row| app.entries.entry(row).timestamp.unwrap())
+            .collect::<Vec<i64>>();
+        assert_eq!(dates, [5, 10, 1, 2]);
+    }
+
+    #[test]
+    fn test_sort_rows_by_level() {
+        let severities = [
+            Some(Severity::Info),
+            None,
+            Some(Severity::Error),
+            Some(Severity::Info),
+            Some(Severity::Fatal),
+            Some(Severity::Error),
+        ];
+        let mut sorted = rows(0, &[0, 1, 2, 3, 4, 5]);
+        sort_rows_by_level(&mut sorted, |row| severities[row.index]);
+        // Rows of the same level stay in file order
+        assert_eq!(indices(&sorted), vec![4, 2, 5, 0, 3, 1]);
+    }
+}
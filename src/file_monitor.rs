@@ -5,7 +5,7 @@ use std::sync::{mpsc, Arc, Mutex};
 use crate::app::App;
 use crate::event::Event;
 use crate::parser::parse_log_by_path;
-use crate::tab::{TabType, TableItems};
+use crate::tab::TabType;
 
 pub struct FileMonitor {
     handler: thread::JoinHandle<()>,
@@ -28,7 +28,7 @@ impl FileMonitor {
                 continue;
             }
 
-            let file_paths_and_sizes = {
+            let (file_paths_and_sizes, parser_settings) = {
                 let app = app.lock().unwrap();
                 let current_tab = &app.tabs()[app.selected_tab_index()];
                 let file_paths_and_sizes = if let TabType::Combined = current_tab.tab_type {
@@ -42,7 +42,7 @@ impl FileMonitor {
                     vec![(current_tab.file_path.clone(), current_tab.last_file_size)]
                 };
 
-                file_paths_and_sizes
+                (file_paths_and_sizes, app.parser_settings().clone())
             };
 
             let mut file_path_to_log_entries = std::collections::HashMap::new();
@@ -58,14 +58,16 @@ impl FileMonitor {
                     continue;
                 }
 
-                if let Ok(log_entries) = parse_log_by_path(&file_path) {
+                if let Ok(log_entries) = parse_log_by_path(&file_path, &parser_settings) {
                     file_path_to_log_entries
                         .insert(file_path.clone(), (log_entries, current_file_size));
                 }
             }
 
             let mut any_tabs_updated = false;
-            for tab in app.lock().unwrap().tabs_mut() {
+            let mut app_lock = app.lock().unwrap();
+            let mut updated_entries = vec![];
+            for tab in app_lock.tabs_mut() {
                 if let crate::tab::TabType::Combined = tab.tab_type {
                     continue;
                 }
@@ -79,10 +81,7 @@ impl FileMonitor {
                     .take()
                     .unwrap();
 
-                *tab.items_mut() = TableItems {
-                    selected_item_index: data.len() - 1,
-                    data,
-                };
+                updated_entries.push((tab.id, data));
 
                 tab.last_file_size = file_size as usize;
 
@@ -90,9 +89,9 @@ impl FileMonitor {
             }
 
             if any_tabs_updated {
-                let filter_text = app.lock().unwrap().filter_input_text().to_string().clone();
-                let mut app = app.lock().unwrap();
-                app.filter_by_current_input(filter_text);
+                // Entries added at the end of the files are merged in without filtering everything again
+                app_lock.update_tab_entries(updated_entries);
+                drop(app_lock);
                 sender.send(Event::Tick).unwrap();
             }
         });
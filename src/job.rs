@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use crate::event::Event;
+
+/// Work (e.g. filtering a large log) running on its own thread, sending what it finds in chunks
+/// so that the UI can show partial results. Dropping the job cancels it.
+pub struct Job<T> {
+    cancelled: Arc<AtomicBool>,
+    receiver: Receiver<T>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// The side of a [`Job`] running on the worker thread.
+pub struct JobContext<T> {
+    cancelled: Arc<AtomicBool>,
+    sender: Sender<T>,
+    /// Wakes the UI up so that it shows the new results without waiting for the next input event
+    event_sender: Sender<Event>,
+}
+
+impl<T: Send + 'static> Job<T> {
+    pub fn spawn<F>(event_sender: Sender<Event>, work: F) -> Job<T>
+    where
+        F: FnOnce(&JobContext<T>) + Send + 'static,
+    {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel();
+        let context = JobContext {
+            cancelled: Arc::clone(&cancelled),
+            sender,
+            event_sender,
+        };
+        let thread = thread::spawn(move || work(&context));
+
+        Job {
+            cancelled,
+            receiver,
+            thread: Some(thread),
+        }
+    }
+
+    /// Cancels the job and waits for its thread to stop, so that it no longer holds what it was given.
+    pub fn cancel(mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            // A job that panicked is stopped too
+            let _ = thread.join();
+        }
+    }
+
+    /// Results sent since the last call, without waiting for more.
+    pub fn results(&self) -> Vec<T> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl<T> Drop for Job<T> {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl<T> JobContext<T> {
+    /// Whether a newer job replaced this one (or the app is closing), in which case the work should stop.
+    pub fn cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Sends a result, returning false when nobody is waiting for it anymore.
+    pub fn send(&self, result: T) -> bool {
+        if self.cancelled() || self.sender.send(result).is_err() {
+            return false;
+        }
+
+        // The UI may be closing, in which case there is nothing left to wake up
+        let _ = self.event_sender.send(Event::Tick);
+        true
+    }
+}
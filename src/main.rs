@@ -17,7 +17,11 @@ use std::{
 use anyhow::Result;
 use std::io::stdout;
 
+mod job;
+mod matcher;
 mod parser;
+mod query;
+mod timezone;
 
 /// Application.
 mod app;
@@ -126,14 +130,15 @@ fn run() -> Result<()> {
     )?;
 
     // create app and run it
+    let events_thread = EventHandler::new();
     let app = std::sync::Arc::new(std::sync::Mutex::new(App::new(
         args.iter()
             .skip(1)
             .filter(|item| !item.is_empty())
             .cloned()
             .collect::<Vec<String>>(),
+        events_thread.sender.clone(),
     )));
-    let events_thread = EventHandler::new();
     let file_monitor_thread = FileMonitor::new(Arc::clone(&app), events_thread.sender.clone());
     let connections_thread = NetHandler::new(Arc::clone(&app));
 
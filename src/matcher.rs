@@ -0,0 +1,170 @@
+use std::fmt;
+use std::ops::Range;
+
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+
+/// How the text of the filter and search inputs is matched against log entries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Comma-separated keywords matched case-insensitively anywhere in the text
+    #[default]
+    Plain,
+    /// Comma-separated keywords matched with their exact case
+    CaseSensitive,
+    /// Comma-separated keywords matched case-insensitively as whole words
+    WholeWord,
+    /// The whole input is a regular expression (so commas are matched literally)
+    Regex,
+    /// The input is a [`Query`](crate::query::Query), only used by the filter (other inputs match it like `Plain`)
+    Query,
+}
+
+impl MatchMode {
+    pub fn next(self) -> MatchMode {
+        match self {
+            MatchMode::Plain => MatchMode::CaseSensitive,
+            MatchMode::CaseSensitive => MatchMode::WholeWord,
+            MatchMode::WholeWord => MatchMode::Regex,
+            MatchMode::Regex => MatchMode::Query,
+            MatchMode::Query => MatchMode::Plain,
+        }
+    }
+
+    /// Reads the mode names used in the config file (the ones shown by `Display`).
+    pub fn from_text(text: &str) -> Option<MatchMode> {
+        [
+            MatchMode::Plain,
+            MatchMode::CaseSensitive,
+            MatchMode::WholeWord,
+            MatchMode::Regex,
+            MatchMode::Query,
+        ]
+        .into_iter()
+        .find(|mode| mode.to_string() == text)
+    }
+}
+
+impl fmt::Display for MatchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchMode::Plain => write!(f, "plain"),
+            MatchMode::CaseSensitive => write!(f, "case-sensitive"),
+            MatchMode::WholeWord => write!(f, "whole word"),
+            MatchMode::Regex => write!(f, "regex"),
+            MatchMode::Query => write!(f, "query"),
+        }
+    }
+}
+
+/// The text of an input compiled for its [`MatchMode`], matching when any of its patterns does.
+pub struct Matcher {
+    regexes: Vec<Regex>,
+}
+
+impl Matcher {
+    pub fn new(input: &str, mode: MatchMode) -> Result<Matcher> {
+        if mode == MatchMode::Regex {
+            let regexes = if input.is_empty() {
+                vec![]
+            } else {
+                vec![Regex::new(input)?]
+            };
+            return Ok(Matcher { regexes });
+        }
+
+        let regexes = input
+            .split(',')
+            .filter(|keyword| !keyword.is_empty())
+            .map(|keyword| {
+                let mut pattern = regex::escape(keyword);
+                if mode == MatchMode::WholeWord {
+                    // Word boundaries only make sense next to word characters (e.g. not around "-v")
+                    let is_word_char =
+                        |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+                    if is_word_char(keyword.chars().next()) {
+                        pattern = format!(r"\b{}", pattern);
+                    }
+                    if is_word_char(keyword.chars().last()) {
+                        pattern = format!(r"{}\b", pattern);
+                    }
+                }
+
+                RegexBuilder::new(&pattern)
+                    .case_insensitive(mode != MatchMode::CaseSensitive)
+                    .build()
+            })
+            .collect::<Result<Vec<Regex>, regex::Error>>()?;
+
+        Ok(Matcher { regexes })
+    }
+
+    /// Whether there is nothing to match, in which case every entry should be kept.
+    pub fn is_empty(&self) -> bool {
+        self.regexes.is_empty()
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regexes.iter().any(|regex| regex.is_match(text))
+    }
+
+    /// Sorted, non-overlapping byte ranges of the text that match.
+    pub fn find_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges = self
+            .regexes
+            .iter()
+            .flat_map(|regex| regex.find_iter(text).map(|m| m.range()))
+            .filter(|range| !range.is_empty())
+            .collect::<Vec<Range<usize>>>();
+        ranges.sort_by_key(|range| range.start);
+
+        let mut merged = Vec::<Range<usize>>::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::{MatchMode, Matcher};
+
+    #[test]
+    fn test_match_modes() {
+        let text = "Request timeout after 250ms, retrying (attempt 2)";
+        let find_ranges = |matcher: &Matcher| {
+            matcher
+                .find_ranges(text)
+                .iter()
+                .map(|range| (range.start, range.end))
+                .collect::<Vec<(usize, usize)>>()
+        };
+
+        let matcher = Matcher::new("TIMEOUT,missing", MatchMode::Plain).unwrap();
+        assert!(matcher.is_match(text));
+        assert_eq!(find_ranges(&matcher), [(8, 15)]);
+
+        let matcher = Matcher::new("TIMEOUT", MatchMode::CaseSensitive).unwrap();
+        assert!(!matcher.is_match(text));
+
+        let matcher = Matcher::new("time", MatchMode::WholeWord).unwrap();
+        assert!(!matcher.is_match(text));
+        let matcher = Matcher::new("Timeout,(attempt", MatchMode::WholeWord).unwrap();
+        assert_eq!(find_ranges(&matcher), [(8, 15), (38, 46)]);
+
+        let matcher = Matcher::new(r"timeout after \d+ms, retrying", MatchMode::Regex).unwrap();
+        assert!(matcher.is_match(text));
+        let matcher = Matcher::new(r"^Request", MatchMode::Regex).unwrap();
+        assert_eq!(find_ranges(&matcher), [(0, 7)]);
+        assert!(Matcher::new("(unclosed", MatchMode::Regex).is_err());
+
+        assert!(Matcher::new("", MatchMode::Regex).unwrap().is_empty());
+        assert!(Matcher::new(",", MatchMode::Plain).unwrap().is_empty());
+        assert_eq!(MatchMode::from_text("whole word"), Some(MatchMode::WholeWord));
+    }
+}
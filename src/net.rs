@@ -50,17 +50,11 @@ impl NetHandler
                             .unwrap();
                         let mut file_path = String::new();
                         stream.read_to_string(&mut file_path).unwrap();
-                        let table_items = crate::tab::TableItems {
-                            data: crate::parser::parse_log_by_path(&file_path).unwrap_or_default(),
-                            selected_item_index: 0,
-                        };
+                        let parser_settings = app_clone.lock().unwrap().parser_settings().clone();
+                        let entries = crate::parser::parse_log_by_path(&file_path, &parser_settings)
+                            .unwrap_or_default();
                         let mut app_lock = app_clone.lock().unwrap();
-                        app_lock.tabs_mut().push(crate::tab::Tab::new(
-                            file_path.to_string(),
-                            table_items,
-                            crate::tab::TabType::Normal,
-                        ));
-                        *app_lock.selected_tab_index_mut() = app_lock.tabs().len() - 1;
+                        app_lock.add_tab(file_path.to_string(), entries);
                         app_lock.reload_combined_tab();
                     }
                     Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
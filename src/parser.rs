@@ -1,29 +1,481 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::io::Read;
+use std::ops::Index;
 
 use log::info;
 
 use regex::Regex;
 
-pub type LogEntry = Vec<String>;
+use crate::timezone::{parse_utc_offset, Zone};
+
+/// Canonical severity that the level text of every supported format maps onto.
+///
+/// The variants are ordered from the least to the most severe so they can be compared directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Trace,
+    Debug,
+    Info,
+    Notice,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl Severity {
+    /// Maps the level text found in a log line (e.g. `WARNING`, `Info`, `i`) onto a canonical severity.
+    pub fn from_level_text(level: &str) -> Option<Severity> {
+        match level.trim().to_lowercase().as_str() {
+            "t" | "trace" | "trc" | "trce" | "v" | "vrb" | "verbose" | "finest" | "finer" => Some(Severity::Trace),
+            "d" | "debug" | "dbg" | "db" | "dbug" | "fine" | "perf" => Some(Severity::Debug),
+            "i" | "info" | "inf" | "information" | "informational" | "success" => Some(Severity::Info),
+            "n" | "notice" | "default" | "df" => Some(Severity::Notice),
+            "w" | "warn" | "warning" | "wrn" => Some(Severity::Warn),
+            "e" | "error" | "err" | "eror" | "fail" | "severe" | "failure" => Some(Severity::Error),
+            "f" | "fatal" | "ftl" | "a" | "assert" | "fault" | "critical" | "crit" | "alert" | "emerg" | "emergency"
+            | "panic" => Some(Severity::Fatal),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed log entry.
+///
+/// `columns` holds the text shown in the table (indexed by [`LogEntryIndices`]) while the
+/// remaining fields are derived from it while parsing.
+#[derive(Clone, Debug, Default)]
+pub struct LogEntry {
+    pub columns: Vec<String>,
+    pub severity: Option<Severity>,
+    /// Set when `severity` was inferred from the message rather than read from the level column
+    pub severity_inferred: bool,
+    /// Name of the [`LogFormat`] the entry was parsed with
+    pub format: &'static str,
+    /// Run of the app the entry belongs to, starting from 1 for the first run in the file
+    pub session: usize,
+    /// Nanoseconds since the Unix epoch (UTC) when the date could be parsed, see [`resolve_timestamps`]
+    pub timestamp: Option<i64>,
+    /// Where the entry goes among the entries of other files: its timestamp, or the one of the entry with a full
+    /// date before it (after it for the first entries) when its date has no year or it has none, `None` when no
+    /// entry of the file has a full date
+    pub order_timestamp: Option<i64>,
+    /// Seconds east of UTC written with the date (e.g. `Z` or `+01:00`), `None` when the date is in the source time zone
+    pub utc_offset: Option<i64>,
+    /// Nanoseconds added to `timestamp` to correct the clock of the machine that wrote the log
+    pub clock_offset: i64,
+    /// Any other values captured by the format (e.g. `pid`, `tid`, `tag`) in the order they appear in the line
+    pub fields: Vec<(String, String)>,
+    /// Line of the file (starting from 1) the entry starts at
+    pub line: usize,
+}
+
+impl Index<usize> for LogEntry {
+    type Output = String;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.columns[index]
+    }
+}
+
+/// Name of the format of entries imported from delimited (CSV/TSV) files, whose fields are shown as table columns
+pub const DELIMITED_FORMAT: &str = "CSV";
+
+/// Header names (lowercase) of delimited files mapped to the date, level and log columns
+const DELIMITED_DATE_HEADERS: &[&str] = &[
+    "date", "time", "timestamp", "datetime", "@timestamp", "_time", "eventtime", "logged",
+];
+const DELIMITED_LEVEL_HEADERS: &[&str] =
+    &["level", "severity", "loglevel", "log.level", "lvl", "priority"];
+const DELIMITED_LOG_HEADERS: &[&str] = &[
+    "message", "msg", "log", "_raw", "text", "description", "body", "event",
+];
+
+/// Rule used to infer the severity of an entry whose format doesn't carry a level.
+#[derive(Clone, Debug)]
+pub struct LevelRule {
+    pub pattern: Regex,
+    pub severity: Severity,
+}
+
+impl LevelRule {
+    pub fn new(pattern: &str, severity: Severity) -> Result<Self> {
+        Ok(LevelRule {
+            pattern: Regex::new(pattern)?,
+            severity,
+        })
+    }
+}
+
+/// Converts a date written as a number into nanoseconds since the Unix epoch, the unit being guessed from the
+/// number of digits (which works for dates close to now): Unix epoch seconds (optionally with a fraction, e.g.
+/// `1703339045.123`), milliseconds, microseconds, nanoseconds or .NET `DateTime.Ticks` (100 nanosecond intervals
+/// since 0001-01-01).
+fn parse_epoch_timestamp(date: &str) -> Option<i64> {
+    let date = date.trim();
+    let (integer, fraction) = date.split_once('.').unwrap_or((date, ""));
+    if integer.is_empty()
+        || !integer.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let number = integer.parse::<i64>().ok()?;
+    match integer.len() {
+        9..=11 => {
+            // only the first 9 digits fit in nanoseconds
+            let nanos = format!("{:0<9}", &fraction[..fraction.len().min(9)]);
+            number.checked_mul(1_000_000_000)?.checked_add(nanos.parse::<i64>().ok()?)
+        }
+        _ if !fraction.is_empty() => None,
+        12..=14 => number.checked_mul(1_000_000),
+        15..=17 => number.checked_mul(1_000),
+        18 => {
+            const UNIX_EPOCH_TICKS: i64 = 621_355_968_000_000_000;
+            (number - UNIX_EPOCH_TICKS).checked_mul(100)
+        }
+        19 => Some(number),
+        _ => None,
+    }
+}
+
+/// A supported log format: the regex matching the first line of its entries plus per-format behaviour.
+pub struct LogFormat {
+    pub name: &'static str,
+    regex: Regex,
+    level_rules: Vec<LevelRule>,
+    /// Whether an `id` capture restarting from 0 marks a new session
+    session_id_reset: bool,
+    /// Message written by the app when it starts, which marks a new session
+    session_banner: Option<Regex>,
+    /// Number of seconds without any entries after which a new session is assumed
+    session_gap_seconds: Option<i64>,
+    /// Matches the last line of formats whose entries are whole sections (e.g. setupapi's `>>>` / `<<<` blocks)
+    section_end: Option<Regex>,
+    /// Sections have no marker at their start (any line starts one), so only `section_end` ends them, otherwise
+    /// the start of the next section ends the current one when its closing marker is missing
+    headerless_sections: bool,
+    /// Searched in the whole entry to find the date/level when the first line doesn't have them, other captures become fields
+    field_patterns: Vec<Regex>,
+    /// Restricts the format to files with a matching name, for formats too loose to be tried on every file
+    file_name_pattern: Option<Regex>,
+    /// Keeps continuation lines (e.g. stack traces) on their own lines instead of appending them to the first one
+    multiline: bool,
+    /// Derives the severity from the HTTP `status` capture (4xx are warnings and 5xx are errors)
+    http_status: bool,
+    /// Whether the format writes dates as numbers (e.g. Unix epoch seconds)
+    epoch_dates: bool,
+    /// Matches lines so loosely (e.g. any line starting with a number) that they only start entries after an
+    /// entry of the same format, otherwise they're continuation lines of the current entry
+    loose: bool,
+}
+
+impl LogFormat {
+    fn new(name: &'static str, regex: &str) -> Self {
+        LogFormat {
+            name,
+            regex: Regex::new(regex).unwrap(),
+            level_rules: vec![],
+            session_id_reset: false,
+            session_banner: None,
+            session_gap_seconds: None,
+            section_end: None,
+            headerless_sections: false,
+            field_patterns: vec![],
+            file_name_pattern: None,
+            multiline: false,
+            http_status: false,
+            epoch_dates: false,
+            loose: false,
+        }
+    }
+
+    fn with_epoch_dates(mut self) -> Self {
+        self.epoch_dates = true;
+        self
+    }
+
+    fn with_loose_match(mut self) -> Self {
+        self.loose = true;
+        self
+    }
+
+    fn with_http_status(mut self) -> Self {
+        self.http_status = true;
+        self
+    }
+
+    fn with_multiline(mut self) -> Self {
+        self.multiline = true;
+        self
+    }
+
+    fn with_file_name_pattern(mut self, file_name_pattern: &str) -> Self {
+        self.file_name_pattern = Some(Regex::new(file_name_pattern).unwrap());
+        self
+    }
+
+    fn with_section_end(mut self, section_end: &str) -> Self {
+        self.section_end = Some(Regex::new(section_end).unwrap());
+        self
+    }
+
+    fn with_headerless_sections(mut self) -> Self {
+        self.headerless_sections = true;
+        self
+    }
+
+    fn with_field_pattern(mut self, pattern: &str) -> Self {
+        self.field_patterns.push(Regex::new(pattern).unwrap());
+        self
+    }
+
+    fn with_session_id_reset(mut self) -> Self {
+        self.session_id_reset = true;
+        self
+    }
+
+    fn with_session_banner(mut self, banner: &str) -> Self {
+        self.session_banner = Some(Regex::new(banner).unwrap());
+        self
+    }
+
+    fn with_session_gap(mut self, seconds: i64) -> Self {
+        self.session_gap_seconds = Some(seconds);
+        self
+    }
+
+    fn with_level_rules(mut self, rules: &[(&str, Severity)]) -> Self {
+        self.level_rules.extend(
+            rules
+                .iter()
+                .map(|(pattern, severity)| LevelRule::new(pattern, *severity).unwrap()),
+        );
+        self
+    }
+}
+
+/// Settings that change how log files are parsed.
+#[derive(Clone, Default)]
+pub struct ParserSettings {
+    /// Whether a level is inferred from the message of entries that don't carry one
+    pub infer_levels: bool,
+    /// User defined rules keyed by format name, these are tried before the built-in rules of the format
+    pub level_rules: HashMap<String, Vec<LevelRule>>,
+    /// Time zone of the dates without a UTC offset keyed by format name (local time when not set)
+    pub format_zones: HashMap<String, Zone>,
+    /// Time zone of the dates without a UTC offset keyed by file path, overrides `format_zones`
+    pub tab_zones: HashMap<String, Zone>,
+    /// Nanoseconds added to the dates keyed by file path, to line up logs of machines whose clocks differ
+    pub clock_offsets: HashMap<String, i64>,
+}
+
+// Launchers are usually left running, so anything longer than this is most likely a separate run
+const DEFAULT_SESSION_GAP_SECONDS: i64 = 30 * 60;
+
+// Failures that show up in the messages of most installers and launchers
+const GENERIC_LEVEL_RULES: &[(&str, Severity)] = &[
+    (r#"(?i)\b(exception|fatal|crash(ed)?)\b"#, Severity::Error),
+    (r#"(?i)\b(fail(ed|ure|s)?|error)\b"#, Severity::Error),
+    (r#"\bE_FAIL\b|\b(hr|hresult|error code)\s*[:=]?\s*0x8[0-9a-fA-F]{7}\b"#, Severity::Error),
+    (r#"(?i)\bwarn(ing)?\b"#, Severity::Warn),
+];
+
+const MSI_LEVEL_RULES: &[(&str, Severity)] = &[
+    (r#"Return value 3\b"#, Severity::Error),
+    (r#"(?i)error status:\s*[1-9]\d*"#, Severity::Error),
+    (r#"(?i)\bNote: 1: (1708|1603)\b"#, Severity::Error),
+    (r#"(?i)error status:\s*0\b"#, Severity::Info),
+];
+
+const WINDOWS_UPDATE_LEVEL_RULES: &[(&str, Severity)] = &[
+    (r#"(?i)^\s*(\*\s*)?(fatal|failed)\b"#, Severity::Error),
+    (r#"(?i)^\s*warning:"#, Severity::Warn),
+];
+
+const UNITY_LEVEL_RULES: &[(&str, Severity)] = &[
+    (r#"^\w+(\.\w+)*Exception\b|^(Error|Assertion failed)\b|^Crash!!!"#, Severity::Error),
+    (r#"^(Warning|The referenced script .* is missing)\b"#, Severity::Warn),
+];
+
+const GO_LEVEL_RULES: &[(&str, Severity)] = &[
+    (r#"^(panic|fatal error)\b"#, Severity::Fatal),
+];
+
+const EPIC_LEVEL_RULES: &[(&str, Severity)] = &[
+    (r#"^\w+: (Fatal|Error):"#, Severity::Error),
+    (r#"^\w+: Warning:"#, Severity::Warn),
+    (r#"^\w+: (Verbose|VeryVerbose):"#, Severity::Debug),
+];
+
+/// Old names of the formats renamed since, so that the settings saved under the old name keep applying
+const RENAMED_FORMATS: &[(&str, &str)] = &[("EA app vc_redist", "WiX Burn")];
+
+/// Current name of a format the settings may have been saved under.
+pub fn current_format_name(name: &str) -> &str {
+    RENAMED_FORMATS
+        .iter()
+        .find(|(old_name, _)| *old_name == name)
+        .map_or(name, |(_, new_name)| new_name)
+}
 
 lazy_static! {
-    static ref REGEX_PATTERNS : Vec<Regex> = vec![
-        Regex::new(r#"^\s*[^\[]+\[(?P<date>\d{2}:\d{2}:\d{2}:\d+)\]:\s*(?P<log>.*)$"#).unwrap(),                                                        // Windows installer (MSI)
-        Regex::new(r#"^\s*(?P<id>\d+)\s+\[(?P<date>[^\]]+)\]\s+PID:\s*(?P<pid>\d+)\s+TID:\s*(?P<tid>\d+)\s+(?P<level>\w+)\s+(?P<log>.*)$"#).unwrap(),   // EA app
-        Regex::new(r#"^\s*\[[^\]]+\]\[(?P<date>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2})\]i\d+:\s*(?P<log>.*)$"#).unwrap(),                                  // EA app vc_redist
-        Regex::new(r#"^\s*(?P<level>\w+)\s+(?P<date>\d{2}:\d{2}:\d{2}\s+\w+)\s+\(\s+\d+\)\s+(?P<tid>\d+)\s+(?P<log>.*)$"#).unwrap(),                    // EA app IGO
-        Regex::new(r#"^\s*(?P<level>\w+)\s+(?P<date>\d{2}:\d{2}:\d{2}\s+\w+)\s+(?P<tid>\d+)\s+\s+(?P<log>.*)$"#).unwrap(),                              // EA app IGO Proxy
-        Regex::new(r#"^\s*\[(?P<date>\d{4}-\d{2}-\d{2}\s\d{2}:\d{2}:\d{2})\]\s+(?P<log>.*)$"#).unwrap(),                                                // Steam
-        Regex::new(r#"^\s*\[(?P<date>\d{4}\.\d{2}\.\d{2}-\d{2}\.\d{2}\.\d{2}:\d+)\][^\]]+\](?P<log>.*)$"#).unwrap(),                                    // Riot launcher (Valorant) + Epic games
-        Regex::new(r#"^\s*\[(?P<date>[^:]+):(?P<level>\w+):[^\]]+\]\s*(?P<log>.*)$"#).unwrap(),                                                         // CEF
-        Regex::new(r#"^(?P<date>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}.\d{3}Z)\s+(?P<level>\w+)\s+(?P<log>.*)$"#).unwrap(),                                // NodeJS
+    static ref TIMESTAMP_PATTERNS : Vec<Regex> = vec![
+        Regex::new(r#"^(?P<year>\d{4})[-./](?P<month>\d{2})[-./](?P<day>\d{2})[T\s-](?P<hour>\d{2})[:.](?P<minute>\d{2})[:.](?P<second>\d{2})(?:[.:,](?P<fraction>\d+))?"#).unwrap(),   // 2023-12-26T06:41:43.537Z, 2023.10.08-05.40.07:182
+        Regex::new(r#"^(?P<month>\d{2})(?P<day>\d{2})/(?P<hour>\d{2})(?P<minute>\d{2})(?P<second>\d{2})(?:\.(?P<fraction>\d+))?$"#).unwrap(),                                        // 0901/211250.717
+        Regex::new(r#"^(?P<day>\d{2})/(?P<month_name>[A-Za-z]{3})/(?P<year>\d{4}):(?P<hour>\d{2}):(?P<minute>\d{2}):(?P<second>\d{2})"#).unwrap(),                                     // 23/Dec/2023:13:44:05 +0100
+        Regex::new(r#"^(?P<month>\d{2})-(?P<day>\d{2})\s+(?P<hour>\d{2}):(?P<minute>\d{2}):(?P<second>\d{2})(?:\.(?P<fraction>\d+))?$"#).unwrap(),                                  // 12-23 13:44:05.123
+        Regex::new(r#"^(?P<hour>\d{2}):(?P<minute>\d{2}):(?P<second>\d{2})(?::(?P<fraction>\d+))?(?:\s*(?P<am_pm>AM|PM))?$"#).unwrap(),                                                // 20:10:58:904, 02:16:02 AM
+    ];
+
+    static ref UTC_OFFSET_REGEX : Regex = Regex::new(r#":\d{2}(?:[.,:]\d+)?\s*(?:Z|UTC|GMT|(?P<offset>[+-]\d{2}(?::?\d{2})?))$"#).unwrap();
+
+    static ref CRI_LINE_REGEX : Regex = Regex::new(r#"^(?P<time>\d{4}-\d{2}-\d{2}T\S+) (?P<stream>stdout|stderr) (?P<tag>[PF]) ?(?P<log>.*)$"#).unwrap();
+
+    static ref LOG_FORMATS : Vec<LogFormat> = vec![
+        LogFormat::new("MSI", r#"^\s*[^\[]+\[(?P<date>\d{2}:\d{2}:\d{2}:\d+)\]:\s*(?P<log>.*)$"#)                                                      // Windows installer (MSI)
+            .with_level_rules(MSI_LEVEL_RULES)
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("EA app", r#"^\s*(?P<id>\d+)\s+\[(?P<date>[^\]]+)\]\s+PID:\s*(?P<pid>\d+)\s+TID:\s*(?P<tid>\d+)\s+(?P<level>\w+)\s+(?P<log>.*)$"#)
+            .with_session_id_reset(),
+        LogFormat::new("WiX Burn", r#"^\s*\[(?P<pid>[0-9a-fA-F]+):(?P<tid>[0-9a-fA-F]+)\]\[(?P<date>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2})\](?P<level>[a-z])\d+:\s*(?P<log>.*)$"#)   // Bundles such as EA app vc_redist and Visual Studio prerequisites
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_banner(r#"^Burn v\d+"#),
+        LogFormat::new("Visual Studio installer", r#"^\s*\[(?P<pid>[0-9a-fA-F]+):(?P<tid>[0-9a-fA-F]+)\]\[(?P<date>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2})\]\s+(?P<log>.*)$"#)
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("DISM", r#"^(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}),\s+(?P<level>\w+)\s+(?P<component>DISM)\s+(?:.*PID=(?P<pid>\d+)\s+TID=(?P<tid>\d+)\s+)?(?P<log>.*)$"#)
+            .with_session_banner(r#"^DISM\.EXE: .*Version"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("CBS", r#"^(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}),\s+(?P<level>\w+)\s+(?P<component>\w+)\s+(?P<log>.*)$"#)
+            .with_session_banner(r#"^(TI: --- Initializing Trusted Installer ---|Loaded Servicing Stack)"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("setupapi", r#"^>>>\s+\[(?P<log>.*)\]\s*$"#)
+            .with_section_end(r#"^<<<\s+\[Exit status:"#)
+            .with_field_pattern(r#"(?m)^>>>\s+Section start (?P<date>\d{4}/\d{2}/\d{2} \d{2}:\d{2}:\d{2}\.\d+)"#)
+            .with_field_pattern(r#"(?m)^<<<\s+\[Exit status: (?P<level>SUCCESS|FAILURE)"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Windows Update", r#"^(?P<date>\d{4}/\d{2}/\d{2} \d{2}:\d{2}:\d{2}\.\d+)\s+(?P<pid>\d+)\s+(?P<tid>\d+)\s+(?P<component>\S+)\s+(?P<log>.*)$"#)                   // Get-WindowsUpdateLog output
+            .with_level_rules(WINDOWS_UPDATE_LEVEL_RULES)
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_banner(r#"^\* START \*|^WU client version"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Windows Update (legacy)", r#"^(?P<date>\d{4}-\d{2}-\d{2}\t\d{2}:\d{2}:\d{2}:\d{3})\s+(?P<pid>\d+)\s+(?P<tid>[0-9a-fA-F]+)\s+(?P<component>\w+)\s+(?P<log>.*)$"#)
+            .with_level_rules(WINDOWS_UPDATE_LEVEL_RULES)
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_banner(r#"^\* START \*|^WU client version"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("EA app IGO", r#"^\s*(?P<level>\w+)\s+(?P<date>\d{2}:\d{2}:\d{2}\s+\w+)\s+\(\s+\d+\)\s+(?P<tid>\d+)\s+(?P<log>.*)$"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("EA app IGO Proxy", r#"^\s*(?P<level>\w+)\s+(?P<date>\d{2}:\d{2}:\d{2}\s+\w+)\s+(?P<tid>\d+)\s+\s+(?P<log>.*)$"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Steam", r#"^\s*\[(?P<date>\d{4}-\d{2}-\d{2}\s\d{2}:\d{2}:\d{2})\]\s+(?P<log>.*)$"#)
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_banner(r#"(?i)^(startup - updater built|log session started)"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Epic", r#"^\s*\[(?P<date>\d{4}\.\d{2}\.\d{2}-\d{2}\.\d{2}\.\d{2}:\d+)\][^\]]+\](?P<log>.*)$"#)                                  // Riot launcher (Valorant) + Epic games
+            .with_level_rules(EPIC_LEVEL_RULES)
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_banner(r#"^LogInit: Build:"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Battle.net Agent", r#"^\[(?P<level>[IWEDF]) (?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d+)\]\s*(?P<log>.*)$"#)
+            .with_session_banner(r#"^Agent version"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Battle.net", r#"^(?P<level>[IWEDF]) (?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d+) \[(?P<component>[^\]]*)\]\s*(?P<log>.*)$"#)
+            .with_session_banner(r#"Battle\.net version"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Ubisoft Connect", r#"^\[(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d+)\]\s+\[\s*(?P<tid>\d+)\]\s+\[(?P<level>\w+)\]\s*(?P<log>.*)$"#)
+            .with_session_banner(r#"(?i)^\[launcher\] starting"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("GOG Galaxy", r#"^(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d+) \[(?P<level>\w+)\]\[\s*\(\d+\)\] \[TID (?P<tid>\d+)\]\[(?P<component>[^\]]*)\]:\s*(?P<log>.*)$"#)
+            .with_session_banner(r#"^Galaxy Client version"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Xbox app", r#"^\[(?P<date>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2})?)\]\s*\[(?P<level>\w+)\]\s*\[(?P<component>[^\]]*)\]\s*(?P<log>.*)$"#)     // Xbox app and GamingServices
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Epoch", r#"^(?P<date>\d{9,11}(?:\.\d{1,9})?|\d{12,19})\s+(?:\[?(?P<level>(?i:trace|debug|info|notice|warn|warning|error|fatal|critical))\]?:?\s+)?(?P<log>.*)$"#)   // Unix epoch seconds/milliseconds/microseconds/nanoseconds or .NET ticks
+            .with_epoch_dates()
+            .with_loose_match()
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("CRI", r#"^(?P<date>\d{4}-\d{2}-\d{2}T\S+) (?P<stream>stdout|stderr) F (?P<log>.*)$"#)                        // Kubernetes CRI (also used for container messages in no known format)
+            .with_multiline()
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Access log", r#"^(?P<remote_addr>\S+) \S+ (?P<user>\S+) \[(?P<date>\d{2}/\w{3}/\d{4}:\d{2}:\d{2}:\d{2} [+-]\d{4})\] "(?P<log>(?P<method>[A-Z]+) (?P<path>\S+)(?: (?P<protocol>[^"]*))?|[^"]*)" (?P<status>\d{3}) (?P<bytes>\d+|-)(?: "(?P<referrer>[^"]*)" "(?P<user_agent>[^"]*)")?(?: (?P<duration>\d+(?:\.\d+)?))?\s*$"#)   // Apache/nginx combined and common formats
+            .with_http_status()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Python", r#"^(?P<level>CRITICAL|ERROR|WARNING|INFO|DEBUG):(?P<component>[^:]*):(?P<log>.*)$"#)                                             // logging.basicConfig() default
+            .with_multiline(),
+        LogFormat::new("Python (asctime)", r#"^(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2},\d{3}) - (?P<component>.+?) - (?P<level>CRITICAL|ERROR|WARNING|INFO|DEBUG) - (?P<log>.*)$"#)
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Spring Boot", r#"^(?P<date>\d{4}-\d{2}-\d{2}[ T]\d{2}:\d{2}:\d{2}[.,]\d{3}(?:Z|[+-]\d{2}:?\d{2})?)\s+(?P<level>TRACE|DEBUG|INFO|WARN|ERROR|FATAL)\s+(?P<pid>\d+)\s+---\s+\[(?P<thread>[^\]]+)\]\s+(?P<component>\S+)\s*:\s(?P<log>.*)$"#)
+            .with_multiline()
+            .with_session_banner(r#"^Starting \S+ (v\S+ )?using Java"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("log4j", r#"^(?P<date>(?:\d{4}-\d{2}-\d{2}[ T])?\d{2}:\d{2}:\d{2}[.,]\d{3})\s+\[(?P<thread>[^\]]+)\]\s+(?P<level>TRACE|DEBUG|INFO|WARN|ERROR|FATAL)\s+(?P<component>\S+)\s+-\s(?P<log>.*)$"#)      // log4j2 and logback defaults
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("log4j (level first)", r#"^(?P<level>TRACE|DEBUG|INFO|WARN|ERROR|FATAL)\s+(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}[.,]\d{3})\s+\[(?P<thread>[^\]]+)\]\s+(?P<component>\S+)\s+-\s(?P<log>.*)$"#)
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Serilog", r#"^(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d+ [+-]\d{2}:\d{2}) \[(?P<level>VRB|DBG|INF|WRN|ERR|FTL)\] (?P<log>.*)$"#)           // File sink default
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Serilog (console)", r#"^\[(?P<date>\d{2}:\d{2}:\d{2}) (?P<level>VRB|DBG|INF|WRN|ERR|FTL)\] (?P<log>.*)$"#)
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new(".NET ILogger", r#"^(?:(?P<date>\d{4}-\d{2}-\d{2}[ T]\d{2}:\d{2}:\d{2}(?:\.\d+)?)\s+)?(?P<level>trce|dbug|info|warn|fail|crit): (?P<log>(?P<component>[^\[\s]+)\[(?P<event_id>\d+)\].*)$"#)    // SimpleConsole default, the message is on the following lines
+            .with_multiline()
+            .with_session_banner(r#"^Microsoft\.Hosting\.Lifetime\[14\]"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("env_logger", r#"^\[(?P<date>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?Z) (?P<level>TRACE|DEBUG|INFO|WARN|ERROR)\s+(?P<component>[^\]]+)\] (?P<log>.*)$"#)
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("tracing", r#"^(?P<date>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}\.\d+Z)\s+(?P<level>TRACE|DEBUG|INFO|WARN|ERROR)\s+(?P<component>\S+?):\s(?P<log>.*)$"#)            // tracing-subscriber fmt default
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("nginx error", r#"^(?P<date>\d{4}/\d{2}/\d{2} \d{2}:\d{2}:\d{2}) \[(?P<level>\w+)\] (?P<pid>\d+)#(?P<tid>\d+): (?:\*(?P<connection>\d+) )?(?P<log>.*)$"#)
+            .with_field_pattern(r#", client: (?P<client>[^,]+)"#)
+            .with_field_pattern(r#", request: "(?P<method>[A-Z]+) (?P<path>\S+)"#)
+            .with_session_banner(r#"^start worker processes"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Go", r#"^(?P<date>\d{4}/\d{2}/\d{2} \d{2}:\d{2}:\d{2}(?:\.\d+)?) (?:(?P<component>[\w\-.]+\.go:\d+): )?(?P<log>.*)$"#)                            // Standard library log package
+            .with_multiline()
+            .with_level_rules(GO_LEVEL_RULES)
+            .with_level_rules(GENERIC_LEVEL_RULES)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("logcat", r#"^(?P<date>(?:\d{4}-)?\d{2}-\d{2}\s+\d{2}:\d{2}:\d{2}\.\d{3})\s+(?P<pid>\d+)\s+(?P<tid>\d+)\s+(?P<level>[VDIWEFA])\s+(?P<tag>.*?)\s*:\s(?P<log>.*)$"#)       // adb logcat -v threadtime (default)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("logcat (brief)", r#"^(?P<level>[VDIWEFA])/(?P<tag>[^(]+?)\(\s*(?P<pid>\d+)\):\s(?P<log>.*)$"#),
+        LogFormat::new("logcat (long)", r#"^\[ (?P<date>(?:\d{4}-)?\d{2}-\d{2}\s+\d{2}:\d{2}:\d{2}\.\d{3})\s+(?P<pid>\d+):\s*(?P<tid>\w+)\s+(?P<level>[VDIWEFA])/(?P<tag>.*?)\s*\]$"#)  // the message is on the following lines
+            .with_section_end(r#"^\s*$"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("log show", r#"^(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d+[+-]\d{4})\s+(?P<tid>0x[0-9a-f]+)\s+(?P<level>\w+)\s+(?P<activity>0x[0-9a-f]+)\s+(?P<pid>\d+)\s+(?P<ttl>\d+)\s+(?P<process>[^:]+?):\s(?P<log>.*)$"#)  // iOS/macOS unified log
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("log show (compact)", r#"^(?P<date>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d+)\s+(?P<level>Df|Db|I|E|F)\s+(?P<process>[^\[]+)\[(?P<pid>\d+):(?P<tid>[0-9a-f]+)\]\s+(?P<log>.*)$"#)
+            .with_multiline()
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("CEF", r#"^\s*\[(?P<date>[^:]+):(?P<level>\w+):[^\]]+\]\s*(?P<log>.*)$"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("NodeJS", r#"^(?P<date>\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}.\d{3}Z)\s+(?P<level>\w+)\s+(?P<log>.*)$"#)
+            .with_session_gap(DEFAULT_SESSION_GAP_SECONDS),
+        LogFormat::new("Unity", r#"^(?P<log>\S.*)$"#)                                                                                                    // Player.log, entries are separated by empty lines
+            .with_file_name_pattern(r#"^(Player|Editor)(-prev)?\.log$"#)
+            .with_section_end(r#"^\s*$"#)
+            .with_headerless_sections()
+            .with_level_rules(UNITY_LEVEL_RULES)
+            .with_session_banner(r#"^(Mono path\[0\]|Initialize engine version)"#),
     ];
 }
 
 pub enum LogEntryIndices {
     FileName,
     // _ID,
+    Session,
     Date,
     // _PID,
     // _TID,
@@ -31,10 +483,688 @@ pub enum LogEntryIndices {
     Log,
 }
 
-fn parse_log_vec(lines: &[&str], log_path: &str) -> Vec<Vec<String>> {
+/// Number of days between 1970-01-01 and the given date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date of the proleptic Gregorian calendar that is the given number of days after 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Reads the UTC offset written after the time of a date (e.g. `2023-12-26T06:41:43.537Z` or `23/Dec/2023:13:44:05 +0100`).
+fn date_utc_offset(date: &str) -> Option<i64> {
+    let captures = UTC_OFFSET_REGEX.captures(date.trim())?;
+    match captures.name("offset") {
+        Some(offset) => parse_utc_offset(offset.as_str()),
+        None => Some(0),
+    }
+}
+
+/// Converts the dates of entries without a UTC offset from the time zone of their tab or format into UTC
+/// and applies the clock offset of the tab.
+pub fn resolve_timestamps(log_entries: &mut [LogEntry], log_path: &str, settings: &ParserSettings) {
+    let clock_offset = settings
+        .clock_offsets
+        .get(log_path)
+        .copied()
+        .unwrap_or_default();
+    let mut order_timestamp = None;
+    let mut first_dated_index = None;
+    for (index, entry) in log_entries.iter_mut().enumerate() {
+        let full_date = if entry.utc_offset.is_none() {
+            let zone = settings
+                .tab_zones
+                .get(log_path)
+                .or_else(|| settings.format_zones.get(entry.format))
+                .copied()
+                .unwrap_or(Zone::Local);
+            let parsed = parse_date_column(&entry[LogEntryIndices::Date as usize]);
+            entry.timestamp = parsed
+                .map(|(timestamp, _)| zone.to_utc(timestamp));
+            parsed.is_some_and(|(_, has_year)| has_year)
+        } else {
+            entry.timestamp = entry.timestamp.map(|timestamp| timestamp - entry.clock_offset);
+            entry.timestamp.is_some()
+        };
+
+        entry.timestamp = entry.timestamp.map(|timestamp| timestamp + clock_offset);
+        entry.clock_offset = clock_offset;
+
+        // Dates without a year land in 1970, which would put them before every other file
+        if full_date {
+            order_timestamp = entry.timestamp;
+            first_dated_index.get_or_insert(index);
+        }
+        entry.order_timestamp = order_timestamp;
+    }
+
+    if let Some(first_dated_index) = first_dated_index {
+        let first_timestamp = log_entries[first_dated_index].order_timestamp;
+        for entry in &mut log_entries[..first_dated_index] {
+            entry.order_timestamp = first_timestamp;
+        }
+    }
+}
+
+/// Formats nanoseconds since the Unix epoch as a date (in milliseconds) that [`parse_timestamp`] reads back.
+pub fn format_timestamp(timestamp: i64) -> String {
+    let seconds = timestamp.div_euclid(1_000_000_000);
+    let (year, month, day) = civil_from_days(seconds.div_euclid(24 * 60 * 60));
+    let seconds_of_day = seconds.rem_euclid(24 * 60 * 60);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        timestamp.rem_euclid(1_000_000_000) / 1_000_000
+    )
+}
+
+/// Converts the date column of an entry into nanoseconds since the Unix epoch.
+///
+/// Dates without a year (or without a date at all) are treated as if they were in 1970-01-01,
+/// which is good enough to compare entries of the same file.
+pub fn parse_timestamp(date: &str) -> Option<i64> {
+    parse_date_column(date).map(|(timestamp, _)| timestamp)
+}
+
+/// [`parse_timestamp`] along with whether the date has a year.
+fn parse_date_column(date: &str) -> Option<(i64, bool)> {
+    let captures = TIMESTAMP_PATTERNS
+        .iter()
+        .find_map(|pattern| pattern.captures(date.trim()))?;
+    let number = |name: &str, default: i64| {
+        captures
+            .name(name)
+            .map_or(Some(default), |m| m.as_str().parse::<i64>().ok())
+    };
+
+    let mut hour = number("hour", 0)?;
+    match captures.name("am_pm").map(|m| m.as_str()) {
+        Some("AM") if hour == 12 => hour = 0,
+        Some("PM") if hour < 12 => hour += 12,
+        _ => {}
+    }
+
+    let nanos = captures.name("fraction").map_or(0, |m| {
+        // only the first 9 digits fit in nanoseconds
+        let digits = &m.as_str()[..std::cmp::min(9, m.as_str().len())];
+        digits.parse::<i64>().unwrap_or(0) * 10_i64.pow(9 - digits.len() as u32)
+    });
+
+    let month = match captures.name("month_name") {
+        Some(month_name) => {
+            const MONTH_NAMES: [&str; 12] = [
+                "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+            ];
+            MONTH_NAMES
+                .iter()
+                .position(|name| month_name.as_str().eq_ignore_ascii_case(name))? as i64
+                + 1
+        }
+        None => number("month", 1)?,
+    };
+
+    let days = days_from_civil(number("year", 1970)?, month, number("day", 1)?);
+    let seconds = ((days * 24 + hour) * 60 + number("minute", 0)?) * 60 + number("second", 0)?;
+    Some((seconds * 1_000_000_000 + nanos, captures.name("year").is_some()))
+}
+
+fn find_format<'a>(
+    line: &'a str,
+    file_name: &str,
+) -> Option<(&'static LogFormat, regex::Captures<'a>)> {
+    LOG_FORMATS
+        .iter()
+        .filter(|format| match &format.file_name_pattern {
+            Some(pattern) => pattern.is_match(file_name),
+            None => true,
+        })
+        .find_map(|format| format.regex.captures(line).map(|captures| (format, captures)))
+}
+
+/// Infers the severity of entries that don't carry a level (or clears inferred severities when disabled).
+pub fn apply_level_inference(log_entries: &mut [LogEntry], settings: &ParserSettings) {
+    for entry in log_entries.iter_mut() {
+        if entry.severity_inferred {
+            entry.severity = None;
+            entry.severity_inferred = false;
+        }
+
+        if !settings.infer_levels || entry.severity.is_some() {
+            continue;
+        }
+
+        let log = &entry[LogEntryIndices::Log as usize];
+        let built_in_rules = LOG_FORMATS
+            .iter()
+            .find(|format| format.name == entry.format)
+            .map_or(&[][..], |format| &format.level_rules[..]);
+        let user_rules = settings
+            .level_rules
+            .get(entry.format)
+            .map_or(&[][..], |rules| &rules[..]);
+
+        if let Some(rule) = user_rules
+            .iter()
+            .chain(built_in_rules.iter())
+            .find(|rule| rule.pattern.is_match(log))
+        {
+            entry.severity = Some(rule.severity);
+            entry.severity_inferred = true;
+        }
+    }
+}
+
+/// A line of a container runtime log with the partial lines already rejoined.
+struct ContainerRecord {
+    line: usize,
+    time: String,
+    stream: String,
+    message: String,
+}
+
+/// Time, stream, message and whether the message continues in the next record of the stream
+type RawContainerRecord = (String, String, String, bool);
+
+/// Unwraps the records of a Docker json-file or Kubernetes CRI log, `None` when the lines are neither.
+fn unwrap_container_records(lines: &[&str]) -> Option<Vec<ContainerRecord>> {
+    let parse_docker = |line: &str| {
+        let value = serde_json::from_str::<serde_json::Value>(line).ok()?;
+        let log = value.get("log")?.as_str()?;
+        let text = |name: &str| value.get(name).and_then(|v| v.as_str()).unwrap_or_default();
+        // Docker ends every complete line with a new line, the ones without it are partial
+        let message = log
+            .strip_suffix('\n')
+            .map(|log| log.strip_suffix('\r').unwrap_or(log));
+        Some((
+            text("time").to_owned(),
+            text("stream").to_owned(),
+            message.unwrap_or(log).to_owned(),
+            message.is_none(),
+        ))
+    };
+    let parse_cri = |line: &str| {
+        let captures = CRI_LINE_REGEX.captures(line)?;
+        Some((
+            captures["time"].to_owned(),
+            captures["stream"].to_owned(),
+            captures["log"].to_owned(),
+            &captures["tag"] == "P",
+        ))
+    };
+
+    let first_line = lines.iter().find(|line| !line.is_empty())?;
+    let parse_record: &dyn Fn(&str) -> Option<RawContainerRecord> =
+        if parse_docker(first_line).is_some() {
+            &parse_docker
+        } else if parse_cri(first_line).is_some() {
+            &parse_cri
+        } else {
+            return None;
+        };
+
+    let mut records = Vec::<ContainerRecord>::new();
+    // Index of the record each stream's partial message continues in
+    let mut partial_records = HashMap::<String, usize>::new();
+    for (line_num, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let Some((time, stream, message, is_partial)) = parse_record(line) else {
+            info!("Error parsinig container log line: [{}]", line);
+            continue;
+        };
+
+        // Partial records are rejoined with the next record of the same stream
+        let index = match partial_records.remove(&stream) {
+            Some(index) => {
+                records[index].message += &message;
+                index
+            }
+            None => {
+                records.push(ContainerRecord {
+                    line: line_num + 1,
+                    time,
+                    stream: stream.clone(),
+                    message,
+                });
+                records.len() - 1
+            }
+        };
+
+        if is_partial {
+            partial_records.insert(stream, index);
+        }
+    }
+
+    Some(records)
+}
+
+/// Parses the messages of container records with the known formats, falling back to the `CRI` format.
+fn parse_container_records(
+    records: &[ContainerRecord],
+    log_path: &str,
+    settings: &ParserSettings,
+) -> Vec<LogEntry> {
+    // Stream of the entry the previous record belongs to and whether its first record is in a known format
+    let mut current_entry: Option<(&str, bool)> = None;
+    let inner_lines = records
+        .iter()
+        .map(|record| {
+            let known_format = matches!(
+                find_format(&record.message, ""),
+                Some((format, _)) if format.name != "CRI"
+            );
+            // Records in no known format that follow an entry of the same stream are its next lines (e.g. stack
+            // traces), which only join entries in no known format themselves when they're indented
+            let continues = !known_format
+                && current_entry.is_some_and(|(stream, known_format)| {
+                    stream == record.stream
+                        && (known_format || record.message.starts_with(char::is_whitespace))
+                });
+            if !continues {
+                current_entry = Some((&record.stream, known_format));
+            }
+
+            if known_format || continues {
+                record.message.clone()
+            } else {
+                format!("{} {} F {}", record.time, record.stream, record.message)
+            }
+        })
+        .collect::<Vec<String>>();
+
+    let mut log_entries = parse_lines(
+        &inner_lines.iter().map(|line| line.as_str()).collect::<Vec<&str>>(),
+        log_path,
+        settings,
+    );
+    for entry in log_entries.iter_mut() {
+        let record = &records[entry.line - 1];
+        if !entry.fields.iter().any(|(name, _)| name == "stream") {
+            entry.fields.insert(0, ("stream".to_owned(), record.stream.clone()));
+        }
+        entry.line = record.line;
+    }
+
+    log_entries
+}
+
+/// Text of an OTLP `AnyValue` (e.g. `{"stringValue": "..."}`), nested values are kept as JSON.
+fn otlp_value_text(value: &serde_json::Value) -> String {
+    let Some((_, inner)) = value.as_object().and_then(|object| object.iter().next()) else {
+        return String::new();
+    };
+
+    match inner {
+        serde_json::Value::String(text) => text.clone(),
+        _ => inner.to_string(),
+    }
+}
+
+/// Keys and texts of an OTLP attribute list (`[{"key": "...", "value": {...}}]`).
+fn otlp_attributes(value: &serde_json::Value) -> Vec<(String, String)> {
+    value["attributes"]
+        .as_array()
+        .map_or(&[][..], |attributes| &attributes[..])
+        .iter()
+        .filter_map(|attribute| {
+            Some((
+                attribute["key"].as_str()?.to_owned(),
+                otlp_value_text(&attribute["value"]),
+            ))
+        })
+        .collect()
+}
+
+/// Parses an OpenTelemetry OTLP JSON export (either a single document or one export per line),
+/// `None` when the lines are not one.
+fn parse_otlp(lines: &[&str], log_path: &str, settings: &ParserSettings) -> Option<Vec<LogEntry>> {
+    let first_line = lines.iter().find(|line| !line.trim().is_empty())?;
+    if !first_line.trim_start().starts_with('{') {
+        return None;
+    }
+
+    let text = lines.join("\n");
+    if !text.contains("\"resourceLogs\"") {
+        return None;
+    }
+
+    let file_name = std::path::Path::new(log_path)
+        .file_name()
+        .unwrap_or_default()
+        .to_str()
+        .unwrap_or_default()
+        .to_string();
+    let number = |value: &serde_json::Value| {
+        // 64 bit integers are written as strings in OTLP JSON
+        value
+            .as_i64()
+            .or_else(|| value.as_str().and_then(|text| text.parse::<i64>().ok()))
+    };
+
+    let mut log_entries = Vec::<LogEntry>::new();
+    let mut exports = serde_json::Deserializer::from_str(&text).into_iter::<serde_json::Value>();
+    let mut any_export = false;
+    // Line of the export and the offset it was counted up to, exports being counted from the previous one
+    let (mut line, mut line_offset) = (1, 0);
+    loop {
+        let offset = exports.byte_offset();
+        let Some(Ok(export)) = exports.next() else {
+            break;
+        };
+        // The offset is right after the previous export, before the new lines separating them
+        let start = text.len() - text[offset..].trim_start().len();
+        line += text[line_offset..start].matches('\n').count();
+        line_offset = start;
+
+        // Other JSON values (e.g. a metrics export in the same file) are skipped
+        let Some(all_resource_logs) = export["resourceLogs"].as_array() else {
+            continue;
+        };
+        any_export = true;
+
+        for resource_logs in all_resource_logs {
+            let resource_fields = otlp_attributes(&resource_logs["resource"]);
+            for scope_logs in resource_logs["scopeLogs"].as_array().into_iter().flatten() {
+                for record in scope_logs["logRecords"].as_array().into_iter().flatten() {
+                    let timestamp = number(&record["timeUnixNano"])
+                        .filter(|timestamp| *timestamp != 0)
+                        .or_else(|| number(&record["observedTimeUnixNano"]));
+                    let severity = match number(&record["severityNumber"]) {
+                        Some(1..=4) => Some(Severity::Trace),
+                        Some(5..=8) => Some(Severity::Debug),
+                        Some(9..=12) => Some(Severity::Info),
+                        Some(13..=16) => Some(Severity::Warn),
+                        Some(17..=20) => Some(Severity::Error),
+                        Some(21..=24) => Some(Severity::Fatal),
+                        _ => None,
+                    };
+                    let level = record["severityText"].as_str().unwrap_or_default();
+                    let severity = Severity::from_level_text(level).or(severity);
+
+                    let mut fields = resource_fields.clone();
+                    fields.extend(otlp_attributes(record));
+                    for (name, key) in [("trace_id", "traceId"), ("span_id", "spanId")] {
+                        if let Some(id) = record[key].as_str().filter(|id| !id.is_empty()) {
+                            fields.push((name.to_owned(), id.to_owned()));
+                        }
+                    }
+
+                    log_entries.push(LogEntry {
+                        columns: vec![
+                            file_name.clone(),
+                            1.to_string(),
+                            timestamp.map(format_timestamp).unwrap_or_default(),
+                            match (level, severity) {
+                                ("", Some(severity)) => format!("{:?}", severity).to_uppercase(),
+                                _ => level.to_owned(),
+                            },
+                            otlp_value_text(&record["body"]),
+                        ],
+                        severity,
+                        severity_inferred: false,
+                        format: "OTLP",
+                        session: 1,
+                        timestamp,
+                        order_timestamp: None,
+                        utc_offset: timestamp.map(|_| 0),
+                        clock_offset: 0,
+                        fields,
+                        line,
+                    });
+                }
+            }
+        }
+    }
+
+    if !any_export {
+        return None;
+    }
+
+    info!(
+        "found [{}] OTLP log entries in [{}]",
+        log_entries.len(),
+        log_path
+    );
+
+    apply_level_inference(&mut log_entries, settings);
+    Some(log_entries)
+}
+
+/// Splits delimited text into records of fields (with the line each record starts at) following RFC 4180,
+/// so quoted fields can contain delimiters, new lines and doubled quotes.
+fn split_delimited_records(text: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::<(usize, Vec<String>)>::new();
+    let mut record = Vec::<String>::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                '\r' if chars.peek() == Some(&'\n') => {}
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|field| !field.is_empty()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                record.clear();
+                record_line = line;
+            }
+            _ if c == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    record.push(field);
+    if record.iter().any(|field| !field.is_empty()) {
+        records.push((record_line, record));
+    }
+
+    records
+}
+
+/// Header name without the byte order mark Excel writes at the start of the file
+fn delimited_header_name(header: &str) -> &str {
+    header.trim_start_matches('\u{feff}').trim()
+}
+
+/// Parses a CSV/TSV file whose header names the date/level/log columns, `None` when the lines are not one.
+///
+/// Files with a `.csv`/`.tsv` extension are always read as delimited, other files only when their header
+/// has both a date and a log column.
+fn parse_delimited(
+    lines: &[&str],
+    log_path: &str,
+    settings: &ParserSettings,
+) -> Option<Vec<LogEntry>> {
+    let path = std::path::Path::new(log_path);
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let header_line = lines.iter().find(|line| !line.trim().is_empty())?;
+    let header_index = |delimiter: char, names: &[&str]| {
+        split_delimited_records(header_line, delimiter)
+            .first()?
+            .1
+            .iter()
+            .position(|header| {
+                names.contains(&delimited_header_name(header).to_lowercase().as_str())
+            })
+    };
+
+    let delimiter = match extension.as_str() {
+        "csv" => ',',
+        "tsv" | "tab" => '\t',
+        _ => ['\t', ','].into_iter().find(|delimiter| {
+            header_index(*delimiter, DELIMITED_DATE_HEADERS).is_some()
+                && header_index(*delimiter, DELIMITED_LOG_HEADERS).is_some()
+        })?,
+    };
+
+    let file_name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_str()
+        .unwrap_or_default()
+        .to_string();
+    let mut records = split_delimited_records(&lines.join("\n"), delimiter).into_iter();
+    let (_, headers) = records.next()?;
+    let headers = headers
+        .iter()
+        .map(|header| delimited_header_name(header).to_owned())
+        .collect::<Vec<String>>();
+    let date_index = header_index(delimiter, DELIMITED_DATE_HEADERS);
+    let level_index = header_index(delimiter, DELIMITED_LEVEL_HEADERS);
+    let log_index = header_index(delimiter, DELIMITED_LOG_HEADERS);
+
+    let mut log_entries = records
+        .map(|(line, values)| {
+            let value = |index: Option<usize>| {
+                index
+                    .and_then(|index| values.get(index))
+                    .map_or("", |value| value.as_str())
+            };
+            let mut date = value(date_index).to_owned();
+            let level = value(level_index);
+            let mut fields = values
+                .iter()
+                .enumerate()
+                .filter(|(index, value)| {
+                    ![date_index, level_index, log_index].contains(&Some(*index))
+                        && !value.is_empty()
+                })
+                .map(|(index, value)| {
+                    let name = headers
+                        .get(index)
+                        .filter(|header| !header.is_empty())
+                        .cloned()
+                        .unwrap_or_else(|| format!("column{}", index + 1));
+                    (name, value.clone())
+                })
+                .collect::<Vec<(String, String)>>();
+
+            let mut timestamp = parse_timestamp(&date);
+            let mut utc_offset = date_utc_offset(&date);
+            if timestamp.is_none() {
+                // Exports often keep dates as epoch numbers, shown as readable dates like in the `Epoch` format
+                timestamp = parse_epoch_timestamp(&date);
+                if let Some(timestamp) = timestamp {
+                    fields.insert(0, ("timestamp".to_owned(), date));
+                    date = format_timestamp(timestamp);
+                    utc_offset = Some(0);
+                }
+            }
+            let timestamp = timestamp.map(|timestamp| {
+                timestamp - utc_offset.unwrap_or_default() * 1_000_000_000
+            });
+
+            LogEntry {
+                columns: vec![
+                    file_name.clone(),
+                    1.to_string(),
+                    date,
+                    level.to_owned(),
+                    value(log_index).to_owned(),
+                ],
+                severity: Severity::from_level_text(level),
+                severity_inferred: false,
+                format: DELIMITED_FORMAT,
+                session: 1,
+                timestamp,
+                order_timestamp: None,
+                utc_offset,
+                clock_offset: 0,
+                fields,
+                line,
+            }
+        })
+        .collect::<Vec<LogEntry>>();
+
+    info!(
+        "found [{}] delimited log entries in [{}]",
+        log_entries.len(),
+        log_path
+    );
+
+    apply_level_inference(&mut log_entries, settings);
+    Some(log_entries)
+}
+
+fn parse_log_vec(lines: &[&str], log_path: &str, settings: &ParserSettings) -> Vec<LogEntry> {
+    if let Some(mut log_entries) = parse_otlp(lines, log_path, settings) {
+        resolve_timestamps(&mut log_entries, log_path, settings);
+        return log_entries;
+    }
+
+    if let Some(mut log_entries) = parse_delimited(lines, log_path, settings) {
+        resolve_timestamps(&mut log_entries, log_path, settings);
+        return log_entries;
+    }
+
+    let mut log_entries = match unwrap_container_records(lines) {
+        Some(records) => parse_container_records(&records, log_path, settings),
+        None => parse_lines(lines, log_path, settings),
+    };
+    resolve_timestamps(&mut log_entries, log_path, settings);
+    log_entries
+}
+
+fn parse_lines(lines: &[&str], log_path: &str, settings: &ParserSettings) -> Vec<LogEntry> {
     let mut line_num = 0;
-    let mut _session = 0;
-    let mut log_entries = Vec::<Vec<String>>::new();
+    let mut session = 1;
+    let mut log_entries = Vec::<LogEntry>::new();
+    let file_name = std::path::Path::new(log_path)
+        .file_name()
+        .unwrap_or_default()
+        .to_str()
+        .unwrap_or_default()
+        .to_string();
 
     while line_num < lines.len() {
         let mut log = String::new();
@@ -44,96 +1174,200 @@ fn parse_log_vec(lines: &[&str], log_path: &str) -> Vec<Vec<String>> {
             continue;
         }
 
-        let mut captures = None;
-        for regex_pattern in REGEX_PATTERNS.iter() {
-            let mut captures_iter = regex_pattern.captures_iter(line);
-
-            if let Some(tmp) = captures_iter.next() {
-                captures = Some(tmp);
-                break;
-            }
-        }
-
-        if captures.is_none() {
+        let Some((format, captures)) = find_format(line, &file_name) else {
             info!("Error parsinig line: [{}]", line);
             line_num += 1;
             continue;
-        }
+        };
 
-        let captures = captures.unwrap();
-        let _id = line_num;
-        if captures.name("id").map_or("", |m| m.as_str()) == "0" {
-            _session += 1;
-        }
-        let date = &captures.name("date").map_or("", |m| m.as_str());
-        let _pid = &captures.name("pid").map_or("", |m| m.as_str());
-        let _tid = &captures.name("tid").map_or("", |m| m.as_str());
-        let level = &captures.name("level").map_or("", |m| m.as_str());
+        let start_line = line_num;
+        let id = &captures.name("id").map_or("", |m| m.as_str());
+        let mut date = captures.name("date").map_or("", |m| m.as_str()).to_owned();
+        let mut fields = format
+            .regex
+            .capture_names()
+            .flatten()
+            .filter(|name| !["date", "level", "log"].contains(name))
+            .filter_map(|name| {
+                let value = captures.name(name)?.as_str().trim();
+                // "-" is how most formats (e.g. access logs) write a missing value
+                (!value.is_empty() && value != "-").then(|| (name.to_owned(), value.to_owned()))
+            })
+            .collect::<Vec<(String, String)>>();
+        let mut level = captures.name("level").map_or("", |m| m.as_str()).to_owned();
         log += &captures
             .name("log")
             .map_or("".to_owned(), |m| m.as_str().replace('\t', "    "));
 
-        loop {
-            // Deal with multiline log entries where only the 1st line matches the regex.
-            // We append the next lines to the first line and show them as a single log entry
-            if line_num >= lines.len() - 1 {
+        if let Some(section_end) = &format.section_end {
+            // Sections span every line up to the closing marker, whichever format these lines look like
+            while line_num < lines.len() - 1 {
+                line_num += 1;
+                if !format.headerless_sections && format.regex.is_match(lines[line_num]) {
+                    // The closing marker is missing (e.g. the app crashed), the line starts the next section
+                    line_num -= 1;
+                    break;
+                }
+
+                if !log.is_empty() {
+                    log += "\n";
+                }
+                log += lines[line_num];
+
+                if section_end.is_match(lines[line_num]) {
+                    break;
+                }
+            }
+            log.truncate(log.trim_end().len());
+        } else {
+            loop {
+                // Deal with multiline log entries where only the 1st line matches the regex.
+                // We append the next lines to the first line and show them as a single log entry
+                if line_num >= lines.len() - 1 {
+                    break;
+                }
+
+                line_num += 1;
+                let next_line = lines[line_num];
+
+                let continuation = match find_format(next_line, &file_name) {
+                    Some((next_format, _)) => next_format.loose && next_format.name != format.name,
+                    None => true,
+                };
+                if continuation {
+                    // Current line doesn't match any known formats (or only a loose one) so we assume it's a continuation of a multiline log entry
+                    if format.multiline {
+                        if !log.is_empty() {
+                            log += "\n";
+                        }
+                        log += &next_line.replace('\t', "    ");
+                    } else {
+                        log += next_line;
+                    }
+                    continue;
+                }
+
+                // Current line is an actual log line (and not a continuation of a multiline log entry)
+                // So we go back to the previous line and break (so that the current line will be processed as a separate entry)
+                line_num -= 1;
                 break;
             }
+        }
 
-            line_num += 1;
-            let next_line = lines[line_num];
+        for field_pattern in &format.field_patterns {
+            if let Some(field_captures) = field_pattern.captures(&log) {
+                let field = |name: &str| field_captures.name(name).map(|m| m.as_str().to_owned());
+                if date.is_empty() {
+                    date = field("date").unwrap_or_default();
+                }
+                if level.is_empty() {
+                    level = field("level").unwrap_or_default();
+                }
 
-            let mut valid_captures = None;
-            for regex_pattern in REGEX_PATTERNS.iter() {
-                let mut captures = regex_pattern.captures_iter(next_line);
+                for name in field_pattern.capture_names().flatten() {
+                    if ["date", "level", "log"].contains(&name)
+                        || fields.iter().any(|(field_name, _)| field_name == name)
+                    {
+                        continue;
+                    }
 
-                if captures.next().is_some() {
-                    valid_captures = Some(captures);
-                    break;
+                    if let Some(value) = field(name) {
+                        fields.push((name.to_owned(), value));
+                    }
                 }
             }
+        }
+
+        let severity = if format.http_status {
+            fields
+                .iter()
+                .find(|(name, _)| name == "status")
+                .and_then(|(_, status)| match status.chars().next() {
+                    Some('5') => Some(Severity::Error),
+                    Some('4') => Some(Severity::Warn),
+                    Some(_) => Some(Severity::Info),
+                    None => None,
+                })
+        } else {
+            Severity::from_level_text(&level)
+        };
 
-            if valid_captures.is_none() {
-                // Current line doesn't match any known formats so we assume it's a continuation of a multiline log entry
-                log += next_line;
-                continue;
+        let timestamp = if format.epoch_dates {
+            let timestamp = parse_epoch_timestamp(&date);
+            if let Some(timestamp) = timestamp {
+                // The number itself is kept as a field and a readable date is shown instead
+                fields.insert(0, ("timestamp".to_owned(), date.clone()));
+                date = format_timestamp(timestamp);
             }
+            timestamp
+        } else {
+            parse_timestamp(&date)
+        };
+        let utc_offset = if format.epoch_dates {
+            timestamp.map(|_| 0)
+        } else {
+            date_utc_offset(&date)
+        };
+        let timestamp = timestamp
+            .map(|timestamp| timestamp - utc_offset.unwrap_or_default() * 1_000_000_000);
 
-            // Current line is an actual log line (and not a continuation of a multiline log entry)
-            // So we go back to the previous line and break (so that the current line will be processed as a separate entry)
-            line_num -= 1;
-            break;
+        if let Some(previous_entry) = log_entries.last() {
+            let id_reset = format.session_id_reset && *id == "0";
+            let banner = format
+                .session_banner
+                .as_ref()
+                .is_some_and(|banner| banner.is_match(&log));
+            let gap = match (format.session_gap_seconds, previous_entry.timestamp, timestamp) {
+                (Some(gap_seconds), Some(previous), Some(current)) => {
+                    previous_entry.format == format.name
+                        && current - previous > gap_seconds * 1_000_000_000
+                }
+                _ => false,
+            };
+
+            if id_reset || banner || gap {
+                session += 1;
+            }
         }
 
-        log_entries.push(vec![
-                         std::path::Path::new(log_path)
-                         .file_name()
-                         .unwrap_or_default()
-                         .to_str()
-                         .unwrap_or_default()
-                         .to_string(),
-                         // id.to_string(),
-                         // _session.to_string(),
-                         date.to_string(),
-                         // pid.to_string(),
-                         // tid.to_string(),
-                         level.to_string(),
-                         log.to_string(),
-        ]);
+        log_entries.push(LogEntry {
+            columns: vec![
+                file_name.clone(),
+                // id.to_string(),
+                session.to_string(),
+                date.to_string(),
+                // pid.to_string(),
+                // tid.to_string(),
+                level.to_string(),
+                log.to_string(),
+            ],
+            severity,
+            severity_inferred: false,
+            format: format.name,
+            session,
+            timestamp,
+            order_timestamp: None,
+            utc_offset,
+            clock_offset: 0,
+            fields,
+            line: start_line + 1,
+        });
 
         line_num += 1;
     }
 
     info!(
-        "found [{}] log _sessions in [{}]",
+        "found [{}] log entries in [{}] sessions in [{}]",
         log_entries.len(),
+        session,
         log_path
         );
 
+    apply_level_inference(&mut log_entries, settings);
     log_entries
 }
 
-pub fn parse_log_by_path(log_path: &str) -> Result<Vec<LogEntry>> {
+pub fn parse_log_by_path(log_path: &str, settings: &ParserSettings) -> Result<Vec<LogEntry>> {
     info!("Attempting to parse log file [{}]...", log_path);
 
     let mut contents = String::new();
@@ -143,15 +1377,19 @@ pub fn parse_log_by_path(log_path: &str) -> Result<Vec<LogEntry>> {
         contents.lines().collect::<Vec<&str>>()
     };
 
-    Ok(parse_log_vec(&lines, log_path))
+    Ok(parse_log_vec(&lines, log_path, settings))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::parse_log_vec;
+    use crate::parser::{
+        apply_level_inference, current_format_name, parse_log_vec, parse_timestamp,
+        resolve_timestamps, LevelRule, LogEntry, LogEntryIndices, ParserSettings, Severity,
+    };
+    use crate::timezone::Zone;
 
     fn verify_parsed_result(
-        parsed_result: &Vec<Vec<String>>,
+        parsed_result: &[LogEntry],
         num_expected_lines: usize,
         num_expected_cols: usize,
         ) {
@@ -159,7 +1397,7 @@ mod tests {
         if num_expected_lines == 0 {
             return;
         }
-        assert_eq!(parsed_result[0].len(), num_expected_cols);
+        assert_eq!(parsed_result[0].columns.len(), num_expected_cols);
     }
 
     #[test]
@@ -173,8 +1411,8 @@ mod tests {
             "another invalid line",
         ];
 
-        let parsed_result = parse_log_vec(&log_lines, "");
-        verify_parsed_result(&parsed_result, 2, 4);
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 2, 5);
     }
 
     #[test]
@@ -187,8 +1425,8 @@ mod tests {
             "another invalid line",
         ];
 
-        let parsed_result = parse_log_vec(&log_lines, "");
-        verify_parsed_result(&parsed_result, 2, 4);
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 2, 5);
     }
 
     #[test]
@@ -202,8 +1440,8 @@ mod tests {
             "another invalid line",
         ];
 
-        let parsed_result = parse_log_vec(&log_lines, "");
-        verify_parsed_result(&parsed_result, 3, 4);
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
     }
 
     #[test]
@@ -221,8 +1459,8 @@ mod tests {
         "WARN	02:16:02 AM (    3)	 8300              DllMain.cpp: 2191		isIGOSharedMemoryNew=1",
         ];
 
-        let parsed_result = parse_log_vec(&log_lines, "");
-        verify_parsed_result(&parsed_result, 3, 4);
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
     }
 
     #[test]
@@ -234,8 +1472,8 @@ mod tests {
             "INFO	02:19:32 AM	16664	             DX9.cpp:  106		Using display format idx=0 (format=0x00000016 / mode.Format=0x00000016)",
         ];
 
-        let parsed_result = parse_log_vec(&log_lines, "");
-        verify_parsed_result(&parsed_result, 3, 4);
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
     }
 
     #[test]
@@ -253,8 +1491,8 @@ mod tests {
             "[2023-12-23 13:44:05] Apps changed: force all",
         ];
 
-        let parsed_result = parse_log_vec(&log_lines, "");
-        verify_parsed_result(&parsed_result, 6, 4);
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 6, 5);
     }
 
     #[test]
@@ -271,12 +1509,637 @@ mod tests {
             "another invalid line",
         ];
 
-        let parsed_result = parse_log_vec(&log_lines, "");
-        verify_parsed_result(&parsed_result, 3, 4);
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
     }
 
     #[test]
-    fn test_cef_parse() {
+    fn test_battle_net_parse() {
+        let log_lines = vec![
+            "[I 2023-12-23 13:44:05.123456] Agent version: 8580",
+            "an invalid line",
+            "[W 2023-12-23 13:44:05.223456] Failed to load product install for 'pro'",
+            "", // left empty on purpose to ensure the parser can handle empty lines gracefully
+            "[E 2023-12-23 13:44:06.000000] {Main} Unable to connect to server",
+            "I 2023-12-23 13:44:07.123456 [Main] {Main} Battle.net version 2.29.0.14944",
+            "W 2023-12-23 13:44:07.223456 [IgrProtocol] {Main} Account has no game licenses",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 5, 5);
+        assert_eq!(parsed_result[0].format, "Battle.net Agent");
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[3].format, "Battle.net");
+        assert_eq!(parsed_result[4].severity, Some(Severity::Warn));
+    }
+
+    #[test]
+    fn test_ubisoft_connect_parse() {
+        let log_lines = vec![
+            "an invalid line",
+            "[2023-12-23 13:44:05.215] [      6232] [INFO]  [launcher] starting Ubisoft Connect 153.0.10796",
+            "[2023-12-23 13:44:05.301] [      6232] [WARNING] [ClubServices] Request timed out",
+            "", // left empty on purpose to ensure the parser can handle empty lines gracefully
+            "[2023-12-23 13:44:06.002] [      7104] [ERROR] [Download] Failed to write chunk",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert!(parsed_result
+            .iter()
+            .all(|entry| entry.format == "Ubisoft Connect"));
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+    }
+
+    #[test]
+    fn test_gog_galaxy_parse() {
+        let log_lines = vec![
+            "2023-12-23 13:44:05.123 [Information][ (0)] [TID 5232][galaxy_client]: Galaxy Client version 2.0.72.25",
+            "2023-12-23 13:44:05.223 [Warning][ (0)] [TID 5232][galaxy_client]: Overlay is disabled",
+            "another invalid line",
+            "2023-12-23 13:44:06.000 [Error][ (0)] [TID 7400][galaxy_client]: Failed to fetch game details",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert!(parsed_result.iter().all(|entry| entry.format == "GOG Galaxy"));
+        assert_eq!(parsed_result[0].severity, Some(Severity::Info));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+    }
+
+    #[test]
+    fn test_xbox_app_parse() {
+        let log_lines = vec![
+            "[2023-12-23T13:44:05.1234567Z] [Info] [GamingServices] Service started",
+            "[2023-12-23T13:44:05.2234567Z] [Warning] [XboxApp] Token refresh took longer than expected",
+            "an invalid line",
+            "[2023-12-23T13:44:06.0000000Z] [Error] [GamingServices] Install failed with 0x80073CF9",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert!(parsed_result.iter().all(|entry| entry.format == "Xbox app"));
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+    }
+
+    #[test]
+    fn test_unity_player_parse() {
+        let log_lines = vec![
+            "Mono path[0] = 'C:/Games/Example/Example_Data/Managed'",
+            "Initialize engine version: 2021.3.16f1 (4016570cf34f)",
+            "",
+            "Loading scene Main",
+            "UnityEngine.Debug:Log (object)",
+            "(Filename: C:\\buildslave\\unity\\build\\Runtime/Export/Debug/Debug.bindings.h Line: 35)",
+            "",
+            "NullReferenceException: Object reference not set to an instance of an object",
+            "  at PlayerController.Update () [0x00000] in <00000000000000000000000000000000>:0 ",
+            "",
+            "",
+        ];
+
+        // Unity's format is only used for files named like the player (or editor) log
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 0, 5);
+
+        let settings = ParserSettings {
+            infer_levels: true,
+            ..Default::default()
+        };
+        let parsed_result = parse_log_vec(
+            &log_lines,
+            "C:/Users/behna/AppData/LocalLow/Example/Player.log",
+            &settings,
+        );
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert_eq!(parsed_result[0][LogEntryIndices::Log as usize].lines().count(), 2);
+        assert_eq!(parsed_result[1][LogEntryIndices::Log as usize].lines().count(), 3);
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+        assert!(parsed_result[2][LogEntryIndices::Log as usize].ends_with(":0"));
+    }
+
+    #[test]
+    fn test_python_parse() {
+        let log_lines = vec![
+            "an invalid line",
+            "INFO:root:Starting worker",
+            "ERROR:app.worker:Unhandled exception",
+            "Traceback (most recent call last):",
+            "  File \"/srv/app/worker.py\", line 42, in run",
+            "    result = int(payload)",
+            "ValueError: invalid literal for int() with base 10: 'abc'",
+            "2023-12-23 13:44:05,123 - app.api - WARNING - Slow response from upstream",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert_eq!(parsed_result[0].format, "Python");
+        assert_eq!(parsed_result[1].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[1][LogEntryIndices::Log as usize].lines().count(), 5);
+        assert_eq!(parsed_result[2].format, "Python (asctime)");
+        assert_eq!(parsed_result[2].severity, Some(Severity::Warn));
+    }
+
+    #[test]
+    fn test_log4j_parse() {
+        let log_lines = vec![
+            "13:44:05.123 [main] INFO  com.example.App - Started App in 2.1 seconds",
+            "2023-12-23 13:44:06,001 [http-nio-8080-exec-1] ERROR com.example.api.Controller - Request failed",
+            "java.lang.IllegalStateException: Connection closed",
+            "	at com.example.db.Pool.get(Pool.java:120)",
+            "	at com.example.api.Controller.handle(Controller.java:45)",
+            "Caused by: java.io.IOException: Broken pipe",
+            "	... 12 more",
+            "WARN  2023-12-23 13:44:07,000 [main] com.example.Cache - Cache is almost full",
+            "2023-12-23 13:44:08.000  INFO 12345 --- [           main] com.example.App                          : Starting App v1.0 using Java 17.0.2",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 4, 5);
+        assert_eq!(parsed_result[0].format, "log4j");
+        assert_eq!(parsed_result[1].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[1][LogEntryIndices::Log as usize].lines().count(), 6);
+        assert_eq!(parsed_result[2].format, "log4j (level first)");
+        assert_eq!(parsed_result[3].format, "Spring Boot");
+        assert_eq!(
+            parsed_result[3][LogEntryIndices::Log as usize],
+            "Starting App v1.0 using Java 17.0.2"
+        );
+    }
+
+    #[test]
+    fn test_serilog_parse() {
+        let log_lines = vec![
+            "2023-12-23 13:44:05.123 +01:00 [INF] Application starting",
+            "2023-12-23 13:44:06.456 +01:00 [ERR] Failed to process order 42",
+            "System.InvalidOperationException: Sequence contains no elements",
+            "   at System.Linq.ThrowHelper.ThrowNoElementsException()",
+            "[13:44:07 WRN] Retrying in 5 seconds",
+            "[13:44:08 VRB] Connection pool stats",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 4, 5);
+        assert_eq!(parsed_result[1].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[1][LogEntryIndices::Log as usize].lines().count(), 3);
+        assert_eq!(parsed_result[2].format, "Serilog (console)");
+        assert_eq!(parsed_result[2].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[3].severity, Some(Severity::Trace));
+    }
+
+    #[test]
+    fn test_dotnet_ilogger_parse() {
+        let log_lines = vec![
+            "info: Microsoft.Hosting.Lifetime[14]",
+            "      Now listening on: http://localhost:5000",
+            "warn: Microsoft.AspNetCore.HttpsPolicy.HttpsRedirectionMiddleware[3]",
+            "      Failed to determine the https port for redirect.",
+            "fail: Microsoft.AspNetCore.Server.Kestrel[13]",
+            "      Connection id \"0HMVF\", Request id \"0HMVF:00000001\": An unhandled exception was thrown by the application.",
+            "      System.NullReferenceException: Object reference not set to an instance of an object.",
+            "         at Example.Controllers.HomeController.Index() in C:\\src\\HomeController.cs:line 21",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert!(parsed_result.iter().all(|entry| entry.format == ".NET ILogger"));
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Log as usize],
+            "Microsoft.Hosting.Lifetime[14]\n      Now listening on: http://localhost:5000"
+        );
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[2][LogEntryIndices::Log as usize].lines().count(), 4);
+    }
+
+    #[test]
+    fn test_rust_parse() {
+        let log_lines = vec![
+            "[2023-12-23T13:44:05Z INFO  log_viewer_rs::app] Loading config",
+            "[2023-12-23T13:44:05Z WARN  log_viewer_rs::net] Could not connect to port 5000",
+            "2023-12-23T13:44:06.123456Z  INFO my_service::server: listening on 0.0.0.0:8080",
+            "2023-12-23T13:44:07.000001Z ERROR my_service::handler: request failed error=timeout",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 4, 5);
+        assert_eq!(parsed_result[0].format, "env_logger");
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[2].format, "tracing");
+        assert_eq!(
+            parsed_result[2][LogEntryIndices::Log as usize],
+            "listening on 0.0.0.0:8080"
+        );
+        assert_eq!(parsed_result[3].severity, Some(Severity::Error));
+    }
+
+    #[test]
+    fn test_go_parse() {
+        let log_lines = vec![
+            "2009/11/10 23:00:00 Starting server on :8080",
+            "2009/11/10 23:00:01.123456 server.go:42: accepted connection from 10.0.0.1",
+            "2009/11/10 23:00:02 panic: runtime error: index out of range [5] with length 3",
+            "goroutine 1 [running]:",
+            "main.main()",
+            "	/tmp/sandbox/prog.go:8 +0x1d",
+        ];
+
+        let settings = ParserSettings {
+            infer_levels: true,
+            ..Default::default()
+        };
+        let parsed_result = parse_log_vec(&log_lines, "", &settings);
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert!(parsed_result.iter().all(|entry| entry.format == "Go"));
+        assert_eq!(
+            parsed_result[1][LogEntryIndices::Log as usize],
+            "accepted connection from 10.0.0.1"
+        );
+        assert_eq!(parsed_result[2].severity, Some(Severity::Fatal));
+        assert_eq!(parsed_result[2][LogEntryIndices::Log as usize].lines().count(), 4);
+    }
+
+    #[test]
+    fn test_logcat_parse() {
+        let log_lines = vec![
+            "--------- beginning of main",
+            "12-23 13:44:05.123  1234  5678 I ActivityManager: Start proc 4321:com.example/u0a123 for activity",
+            "12-23 13:44:05.223  4321  4321 E AndroidRuntime: FATAL EXCEPTION: main",
+            "12-23 13:44:05.323  4321  4321 F libc    : Fatal signal 11 (SIGSEGV), code 1 (SEGV_MAPERR)",
+            "W/ConnectivityManager( 4321): Network request timed out",
+            "[ 12-23 13:44:06.000  4321: 4400 D/OkHttp ]",
+            "--> GET https://example.com/api",
+            "",
+            "[ 12-23 13:44:06.100  4321: 4400 V/OkHttp ]",
+            "<-- 200 OK (100ms)",
+            "",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 6, 5);
+        assert_eq!(parsed_result[0].format, "logcat");
+        assert_eq!(
+            parsed_result[0].fields,
+            vec![
+                ("pid".to_owned(), "1234".to_owned()),
+                ("tid".to_owned(), "5678".to_owned()),
+                ("tag".to_owned(), "ActivityManager".to_owned()),
+            ]
+        );
+        assert_eq!(parsed_result[1].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Fatal));
+        assert_eq!(parsed_result[2].fields[2], ("tag".to_owned(), "libc".to_owned()));
+        assert_eq!(parsed_result[3].format, "logcat (brief)");
+        assert_eq!(parsed_result[3].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[4].format, "logcat (long)");
+        assert_eq!(
+            parsed_result[4][LogEntryIndices::Log as usize],
+            "--> GET https://example.com/api"
+        );
+        assert_eq!(parsed_result[5].severity, Some(Severity::Trace));
+        assert!(parsed_result[0].timestamp < parsed_result[5].timestamp);
+    }
+
+    #[test]
+    fn test_log_show_parse() {
+        let log_lines = vec![
+            "Timestamp                       Thread     Type        Activity             PID    TTL  ", // parser ignores this line
+            "2023-12-23 13:44:05.123456+0100 0x1a2b3c   Default     0x0                  123    0    kernel: (AppleACPIPlatform) ACPI: sleep states S3 S4 S5",
+            "2023-12-23 13:44:05.223456+0100 0x1a2b3c   Error       0x0                  456    7    backboardd: [com.apple.backboard:hid] Unable to open device",
+            "2023-12-23 13:44:05.323456+0100 0x1a2b3d   Fault       0x0                  456    7    backboardd: Invariant violated",
+            "2023-12-23 13:44:06.000 E  backboardd[456:1a2b3c] [com.apple.backboard:hid] Unable to open device",
+            "2023-12-23 13:44:06.100 Df kernel[0:1a2b3e] (AppleACPIPlatform) ACPI: wake",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 5, 5);
+        assert_eq!(parsed_result[0].format, "log show");
+        assert_eq!(parsed_result[0].severity, Some(Severity::Notice));
+        assert_eq!(parsed_result[1].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Fatal));
+        assert!(parsed_result[1]
+            .fields
+            .contains(&("process".to_owned(), "backboardd".to_owned())));
+        assert_eq!(parsed_result[3].format, "log show (compact)");
+        assert!(parsed_result[3]
+            .fields
+            .contains(&("pid".to_owned(), "456".to_owned())));
+        assert_eq!(parsed_result[4].severity, Some(Severity::Notice));
+    }
+
+    #[test]
+    fn test_access_log_parse() {
+        let log_lines = vec![
+            r#"10.0.0.1 - - [23/Dec/2023:13:44:05 +0100] "GET /index.html HTTP/1.1" 200 5120 "https://example.com/" "Mozilla/5.0 (Windows NT 10.0; Win64; x64)" 0.012"#,
+            r#"10.0.0.2 - alice [23/Dec/2023:13:44:06 +0100] "POST /api/orders HTTP/1.1" 503 -"#,
+            "an invalid line",
+            r#"10.0.0.3 - - [23/Dec/2023:13:44:07 +0100] "GET /missing HTTP/1.1" 404 153 "-" "curl/8.4.0""#,
+            r#"2023/12/23 13:44:07 [error] 1234#5678: *9 open() "/var/www/missing" failed (2: No such file or directory), client: 10.0.0.3, server: example.com, request: "GET /missing HTTP/1.1", host: "example.com""#,
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 4, 5);
+        assert_eq!(parsed_result[0].format, "Access log");
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Log as usize],
+            "GET /index.html HTTP/1.1"
+        );
+        assert_eq!(
+            parsed_result[0].fields,
+            [
+                ("remote_addr", "10.0.0.1"),
+                ("method", "GET"),
+                ("path", "/index.html"),
+                ("protocol", "HTTP/1.1"),
+                ("status", "200"),
+                ("bytes", "5120"),
+                ("referrer", "https://example.com/"),
+                ("user_agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64)"),
+                ("duration", "0.012"),
+            ]
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<Vec<(String, String)>>()
+        );
+        assert_eq!(parsed_result[0].severity, Some(Severity::Info));
+        assert_eq!(parsed_result[1].severity, Some(Severity::Error));
+        assert!(parsed_result[1]
+            .fields
+            .contains(&("user".to_owned(), "alice".to_owned())));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[3].format, "nginx error");
+        assert_eq!(parsed_result[3].severity, Some(Severity::Error));
+        assert!(parsed_result[3]
+            .fields
+            .contains(&("path".to_owned(), "/missing".to_owned())));
+        // The +0100 offset is applied to get UTC
+        assert_eq!(
+            parsed_result[2].timestamp,
+            parse_timestamp("2023-12-23 12:44:07")
+        );
+        assert_eq!(parsed_result[2].utc_offset, Some(3600));
+    }
+
+    #[test]
+    fn test_container_parse() {
+        let docker_lines = vec![
+            r#"{"log":"2023-12-23 13:44:05,123 - app.api - WARNING - Slow response from upstream\n","stream":"stderr","time":"2023-12-23T13:44:05.123456789Z"}"#,
+            r#"{"log":"Listening on ","stream":"stdout","time":"2023-12-23T13:44:06.000000001Z"}"#,
+            "an invalid line",
+            r#"{"log":"port 8080\n","stream":"stdout","time":"2023-12-23T13:44:06.000000002Z"}"#,
+        ];
+
+        let parsed_result = parse_log_vec(&docker_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 2, 5);
+        assert_eq!(parsed_result[0].format, "Python (asctime)");
+        assert_eq!(parsed_result[0].severity, Some(Severity::Warn));
+        assert_eq!(
+            parsed_result[0].fields[0],
+            ("stream".to_owned(), "stderr".to_owned())
+        );
+        assert_eq!(parsed_result[1].format, "CRI");
+        assert_eq!(
+            parsed_result[1][LogEntryIndices::Log as usize],
+            "Listening on port 8080"
+        );
+        assert_eq!(parsed_result[1].line, 2);
+        assert_eq!(
+            parsed_result[1].timestamp,
+            parse_timestamp("2023-12-23 13:44:06.000000001")
+        );
+
+        let cri_lines = vec![
+            "2023-12-23T13:44:05.123456789Z stdout P Listening ",
+            "2023-12-23T13:44:05.223456789Z stderr F ERROR:app.worker:Unhandled exception",
+            "2023-12-23T13:44:05.323456789Z stdout F on port 8080",
+        ];
+
+        let parsed_result = parse_log_vec(&cri_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 2, 5);
+        assert_eq!(parsed_result[0].format, "CRI");
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Log as usize],
+            "Listening on port 8080"
+        );
+        assert_eq!(parsed_result[1].format, "Python");
+        assert_eq!(parsed_result[1].severity, Some(Severity::Error));
+        assert_eq!(
+            parsed_result[1].fields[0],
+            ("stream".to_owned(), "stderr".to_owned())
+        );
+        assert_eq!(parsed_result[1].line, 2);
+    }
+
+    #[test]
+    fn test_container_parse_multiline() {
+        let docker_lines = vec![
+            r#"{"log":"2023-12-23 13:44:05,123 - app.worker - ERROR - Job failed\n","stream":"stderr","time":"2023-12-23T13:44:05.123456789Z"}"#,
+            r#"{"log":"Traceback (most recent call last):\n","stream":"stderr","time":"2023-12-23T13:44:05.123456790Z"}"#,
+            r#"{"log":"  File \"/app/worker.py\", line 12, in run\n","stream":"stderr","time":"2023-12-23T13:44:05.123456791Z"}"#,
+            r#"{"log":"ValueError: bad input\n","stream":"stderr","time":"2023-12-23T13:44:05.123456792Z"}"#,
+            r#"{"log":"Exception in thread \"main\" java.lang.IllegalStateException: closed\n","stream":"stdout","time":"2023-12-23T13:44:06.000000001Z"}"#,
+            r#"{"log":"\tat com.example.Main.run(Main.java:42)\n","stream":"stdout","time":"2023-12-23T13:44:06.000000002Z"}"#,
+            r#"{"log":"\tat com.example.Main.main(Main.java:7)\n","stream":"stdout","time":"2023-12-23T13:44:06.000000003Z"}"#,
+            r#"{"log":"Server stopped\n","stream":"stdout","time":"2023-12-23T13:44:07.000000001Z"}"#,
+        ];
+
+        // Stack traces stay with the entry they belong to
+        let parsed_result = parse_log_vec(&docker_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert_eq!(parsed_result[0].format, "Python (asctime)");
+        assert_eq!(parsed_result[0].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[0][LogEntryIndices::Log as usize].lines().count(), 4);
+        assert!(parsed_result[0][LogEntryIndices::Log as usize].ends_with("ValueError: bad input"));
+        assert_eq!(parsed_result[1].format, "CRI");
+        assert_eq!(parsed_result[1][LogEntryIndices::Log as usize].lines().count(), 3);
+        assert_eq!(parsed_result[1].line, 5);
+        assert_eq!(
+            parsed_result[2][LogEntryIndices::Log as usize],
+            "Server stopped"
+        );
+        assert_eq!(parsed_result[2].line, 8);
+    }
+
+    #[test]
+    fn test_otlp_parse() {
+        let log_lines = vec![
+            "{",
+            r#"  "resourceLogs": [{"#,
+            r#"    "resource": {"attributes": [{"key": "service.name", "value": {"stringValue": "checkout"}}]},"#,
+            r#"    "scopeLogs": [{"#,
+            r#"      "scope": {"name": "checkout.api"},"#,
+            r#"      "logRecords": ["#,
+            r#"        {"timeUnixNano": "1703339045123456789", "severityNumber": 17, "severityText": "Error", "body": {"stringValue": "Payment declined"}, "attributes": [{"key": "http.status_code", "value": {"intValue": "402"}}], "traceId": "5b8efff798038103d269b633813fc60c", "spanId": "eee19b7ec3c1b174"},"#,
+            r#"        {"timeUnixNano": "0", "observedTimeUnixNano": "1703339046000000000", "severityNumber": 13, "body": {"kvlistValue": {"values": []}}}"#,
+            "      ]",
+            "    }]",
+            "  }]",
+            "}",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 2, 5);
+        assert_eq!(parsed_result[0].format, "OTLP");
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Date as usize],
+            "2023-12-23 13:44:05.123"
+        );
+        assert_eq!(parsed_result[0].timestamp, Some(1703339045123456789));
+        assert_eq!(parsed_result[0][LogEntryIndices::Level as usize], "Error");
+        assert_eq!(parsed_result[0].severity, Some(Severity::Error));
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Log as usize],
+            "Payment declined"
+        );
+        assert_eq!(
+            parsed_result[0].fields,
+            [
+                ("service.name", "checkout"),
+                ("http.status_code", "402"),
+                ("trace_id", "5b8efff798038103d269b633813fc60c"),
+                ("span_id", "eee19b7ec3c1b174"),
+            ]
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<Vec<(String, String)>>()
+        );
+        assert_eq!(parsed_result[1][LogEntryIndices::Level as usize], "WARN");
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+        assert_eq!(
+            parsed_result[1].timestamp,
+            parse_timestamp(&parsed_result[1][LogEntryIndices::Date as usize])
+        );
+
+        // One export per line (NDJSON), other values are skipped
+        let log_lines = vec![
+            r#"{"resourceLogs": [{"scopeLogs": [{"logRecords": [{"timeUnixNano": "1703339045000000000", "body": {"stringValue": "first"}}]}]}]}"#,
+            r#"{"resourceMetrics": []}"#,
+            "",
+            r#"{"resourceLogs": [{"scopeLogs": [{"logRecords": [{"timeUnixNano": "1703339046000000000", "body": {"stringValue": "second"}}]}]}]}"#,
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 2, 5);
+        assert_eq!(parsed_result[0].line, 1);
+        assert_eq!(parsed_result[1][LogEntryIndices::Log as usize], "second");
+        assert_eq!(parsed_result[1].line, 4);
+    }
+
+    #[test]
+    fn test_delimited_parse() {
+        let log_lines = vec![
+            "\u{feff}_time,host,severity,_raw",
+            r#"2023-12-23T13:44:05.123+01:00,web-01,ERROR,"Request failed, retrying""#,
+            r#"2023-12-23T13:44:06.000+01:00,,INFO,"Stack:"#,
+            r#"  at ""main"" (app.js:1)""#,
+            "",
+            "2023-12-23T13:44:07.000+01:00,web-02,WARN,Slow response",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "export.csv", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert_eq!(parsed_result[0].format, "CSV");
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Log as usize],
+            "Request failed, retrying"
+        );
+        assert_eq!(parsed_result[0].severity, Some(Severity::Error));
+        assert_eq!(
+            parsed_result[0].fields,
+            [("host".to_owned(), "web-01".to_owned())]
+        );
+        assert_eq!(
+            parsed_result[0].timestamp,
+            parse_timestamp("2023-12-23 12:44:05.123")
+        );
+        assert_eq!(
+            parsed_result[1][LogEntryIndices::Log as usize],
+            "Stack:\n  at \"main\" (app.js:1)"
+        );
+        assert!(parsed_result[1].fields.is_empty());
+        assert_eq!(parsed_result[2].line, 6);
+
+        // Without the extension the header has to name the date and log columns
+        let log_lines = vec![
+            "Timestamp\tLevel\tMessage\tLogger",
+            "2023-12-23 13:44:05\tWarning\tDisk almost full\tStorage",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "export.txt", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 1, 5);
+        assert_eq!(parsed_result[0].severity, Some(Severity::Warn));
+        assert_eq!(
+            parsed_result[0].fields,
+            [("Logger".to_owned(), "Storage".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_epoch_parse() {
+        let log_lines = vec![
+            "1703339045 INFO Server started",
+            "1703339046.5 [warn] Slow response",
+            "1703339047123 error: Request failed",
+            "1703339048000000000 Connection closed",
+            "638390000000000000 Ticks from .NET",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 5, 5);
+        assert_eq!(parsed_result[0].format, "Epoch");
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Date as usize],
+            "2023-12-23 13:44:05.000"
+        );
+        assert_eq!(
+            parsed_result[0].fields,
+            [("timestamp".to_owned(), "1703339045".to_owned())]
+        );
+        assert_eq!(parsed_result[0].severity, Some(Severity::Info));
+        assert_eq!(
+            parsed_result[1].timestamp,
+            Some(1_703_339_046_500_000_000)
+        );
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+        assert_eq!(
+            parsed_result[2][LogEntryIndices::Date as usize],
+            "2023-12-23 13:44:07.123"
+        );
+        assert_eq!(parsed_result[2][LogEntryIndices::Log as usize], "Request failed");
+        assert_eq!(
+            parsed_result[3].timestamp,
+            Some(1_703_339_048_000_000_000)
+        );
+        assert_eq!(parsed_result[3][LogEntryIndices::Level as usize], "");
+        assert_eq!(
+            parsed_result[4][LogEntryIndices::Date as usize],
+            "2023-12-24 07:33:20.000"
+        );
+
+        let log_lines = vec!["time,message", "1703339045123,Exported entry"];
+        let parsed_result = parse_log_vec(&log_lines, "export.csv", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 1, 5);
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Date as usize],
+            "2023-12-23 13:44:05.123"
+        );
+
+        // Lines starting with a number only start entries in files of this format
+        let log_lines = vec![
+            "2023-12-23 13:44:05.123 [Info][ (0)] [TID 12][Net]: Download finished,",
+            "123456789012 bytes written",
+        ];
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 1, 5);
+        assert_eq!(parsed_result[0].format, "GOG Galaxy");
+        assert!(parsed_result[0][LogEntryIndices::Log as usize].ends_with("123456789012 bytes written"));
+    }
+
+    #[test]
+    fn test_cef_parse() {
         let log_lines = vec![
             "an invalid line",
             "[0901/211250.717:ERROR:adm_helpers.cc(62)] Failed to query stereo recording.",
@@ -294,7 +2157,349 @@ mod tests {
             "an invalid line",
         ];
 
-        let parsed_result = parse_log_vec(&log_lines, "");
-        verify_parsed_result(&parsed_result, 6, 4);
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 6, 5);
+        assert_eq!(parsed_result[0].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+    }
+
+    #[test]
+    fn test_cbs_parse() {
+        let log_lines = vec![
+            "2023-12-23 13:44:05, Info                  CBS    TI: --- Initializing Trusted Installer ---",
+            "2023-12-23 13:44:05, Info                  CBS    Loaded Servicing Stack v10.0.19041.3745 with Core: C:\\Windows\\winsxs\\amd64_microsoft-windows-servicingstack_31bf3856ad364e35_10.0.19041.3745_none_7e2d5ed4ac6e2afb\\cbscore.dll",
+            "", // left empty on purpose to ensure the parser can handle empty lines gracefully
+            "2023-12-23 13:44:06, Info                  CSI    00000001@2023/12/23:13:44:06.123 WcpInitialize: wcp.dll version 0.0.0.6 (bld 6.3.9600.16384)",
+            "2023-12-23 13:44:07, Error                 CBS    Failed to resolve package 'Package_for_KB5032189~31bf3856ad364e35~amd64~~19041.3693.1.6' [HRESULT = 0x800f0831 - CBS_E_STORE_CORRUPTION]",
+            "2023-12-23 13:44:07, Warning               CBS    Unrecognized packageExtended attribute.",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 5, 5);
+        assert!(parsed_result.iter().all(|entry| entry.format == "CBS"));
+        assert_eq!(parsed_result[3].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[4].severity, Some(Severity::Warn));
+    }
+
+    #[test]
+    fn test_dism_parse() {
+        let log_lines = vec![
+            "an invalid line",
+            "2023-12-23 13:44:05, Info                  DISM   PID=4560 TID=3612 Scratch directory set to 'C:\\Users\\behna\\AppData\\Local\\Temp\\'. - CDISMManager::put_ScratchDir",
+            "2023-12-23 13:44:05, Info                  DISM   DISM Provider Store: PID=4560 TID=3612 Found and Initialized the DISM Logger. - CDISMProviderStore::Internal_InitializeLogger",
+            "2023-12-23 13:44:06, Error                 DISM   DISM Package Manager: PID=4560 TID=3612 Failed finalizing changes. - CDISMPackageManager::Internal_Finalize(hr:0x800f081f)",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 3, 5);
+        assert!(parsed_result.iter().all(|entry| entry.format == "DISM"));
+        assert_eq!(
+            parsed_result[1][LogEntryIndices::Log as usize],
+            "Found and Initialized the DISM Logger. - CDISMProviderStore::Internal_InitializeLogger"
+        );
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+    }
+
+    #[test]
+    fn test_setupapi_parse() {
+        let log_lines = vec![
+            "[Device Install Log]",                                       // parser ignores this line
+            "     OS Version = 10.0.19045",                               // parser ignores this line
+            "[BeginLog]",                                                 // parser ignores this line
+            "",
+            "[Boot Session: 2023/12/23 13:40:00.500]",                    // parser ignores this line
+            "",
+            ">>>  [Device Install (Hardware initiated) - SWD\\WPDBUSENUM\\_??_USBSTOR#Disk&Ven_Kingston]",
+            ">>>  Section start 2023/12/23 13:44:05.123",
+            "     ump: Creating Install Process: DrvInst.exe 13:44:05.125",
+            "!!!  dvi: Device not started: Device has problem: 0x1c (CM_PROB_FAILED_INSTALL), problem status: 0x00000000.",
+            "<<<  Section end 2023/12/23 13:44:05.500",
+            "<<<  [Exit status: FAILURE(0xe0000219)]",
+            "",
+            ">>>  [Setup Import Driver Package - C:\\Windows\\System32\\DriverStore\\Temp\\{a6b1a4c4}\\wpdfs.inf]",
+            ">>>  Section start 2023/12/23 13:45:00.000",
+            "     inf: Provider: Microsoft",
+            "<<<  Section end 2023/12/23 13:45:00.250",
+            "<<<  [Exit status: SUCCESS]",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 2, 5);
+        assert_eq!(
+            parsed_result[0][LogEntryIndices::Date as usize],
+            "2023/12/23 13:44:05.123"
+        );
+        assert_eq!(parsed_result[0][LogEntryIndices::Level as usize], "FAILURE");
+        assert_eq!(parsed_result[0].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[0][LogEntryIndices::Log as usize].lines().count(), 6);
+        assert_eq!(parsed_result[1].severity, Some(Severity::Info));
+        assert!(parsed_result[1][LogEntryIndices::Log as usize].ends_with("[Exit status: SUCCESS]"));
+    }
+
+    #[test]
+    fn test_setupapi_parse_missing_section_end() {
+        let log_lines = vec![
+            ">>>  [Device Install (Hardware initiated) - USB\\VID_046D&PID_C52B]",
+            ">>>  Section start 2023/12/23 13:44:05.123",
+            "     ump: Creating Install Process: DrvInst.exe 13:44:05.125",
+            ">>>  [Setup Import Driver Package - C:\\Windows\\INF\\usb.inf]",
+            ">>>  Section start 2023/12/23 13:45:00.000",
+            "<<<  Section end 2023/12/23 13:45:00.250",
+            "<<<  [Exit status: SUCCESS]",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 2, 5);
+        assert_eq!(parsed_result[0][LogEntryIndices::Log as usize].lines().count(), 3);
+        assert_eq!(parsed_result[0].severity, None);
+        assert_eq!(
+            parsed_result[1][LogEntryIndices::Date as usize],
+            "2023/12/23 13:45:00.000"
+        );
+        assert_eq!(parsed_result[1].severity, Some(Severity::Info));
+    }
+
+    #[test]
+    fn test_windows_update_parse() {
+        let log_lines = vec![
+            "2023/12/23 13:44:05.1234567 1234  5678  Agent           WU client version 10.0.19041.3636",
+            "2023/12/23 13:44:05.1334567 1234  5678  Agent           Base directory: C:\\Windows\\SoftwareDistribution",
+            "an invalid line",
+            "2023/12/23 13:44:06.0000000 1234  5678  Handler         FATAL: CBS called Error with 0x800f0922,",
+            "2023-12-23	13:44:05:123	 1234	5678	Agent	WU client version 7.6.7601.24436",
+            "2023-12-23	13:44:05:223	 1234	5678	Agent	WARNING: Failed to find updates with error code 80072EFD",
+        ];
+
+        let settings = ParserSettings {
+            infer_levels: true,
+            ..Default::default()
+        };
+        let parsed_result = parse_log_vec(&log_lines, "", &settings);
+        verify_parsed_result(&parsed_result, 5, 5);
+        assert_eq!(parsed_result[0].format, "Windows Update");
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[3].format, "Windows Update (legacy)");
+        assert_eq!(parsed_result[4].severity, Some(Severity::Warn));
+    }
+
+    #[test]
+    fn test_visual_studio_installer_parse() {
+        let log_lines = vec![
+            "[1E78:1CCC][2023-09-03T16:46:28]i001: Burn v3.14.0.8606, Windows v10.0 (Build 19045: Service Pack 0), path: C:\\ProgramData\\Package Cache\\{57a73df6}\\VC_redist.x64.exe",
+            "[1E78:1CCC][2023-09-03T16:46:29]w120: Detected partially cached package: vcRuntimeMinimum_x64",
+            "[1E78:1CCC][2023-09-03T16:46:30]e000: Error 0x80070643: Failed to install MSI package.",
+            "[3f68:0001][2023-09-03T16:47:00] Setup Engine version 3.8.2091.34612",
+            "[3f68:0001][2023-09-03T16:47:01] Command line: install --channelId VisualStudio.17.Release",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 5, 5);
+        assert_eq!(parsed_result[0].format, "WiX Burn");
+        assert_eq!(parsed_result[1].severity, Some(Severity::Warn));
+        assert_eq!(parsed_result[2].severity, Some(Severity::Error));
+        assert_eq!(parsed_result[3].format, "Visual Studio installer");
+        // Settings saved before the format was renamed
+        assert_eq!(current_format_name("EA app vc_redist"), parsed_result[0].format);
+        assert_eq!(current_format_name("Steam"), "Steam");
+    }
+
+    #[test]
+    fn test_level_inference() {
+        let log_lines = vec![
+            "MSI (s) (38:48) [20:10:58:904]: Product: Microsoft Visual C++ 2013 x64 Minimum Runtime - 12.0.40664 -- Installation failed.",
+            "MSI (s) (38:48) [20:10:58:905]: Windows Installer installed the product. Installation success or error status: 1603.",
+            "MSI (s) (38:48) [20:10:58:906]: Note: 1: 1707 ",
+            "MSI (s) (38:48) [20:10:58:907]: Doing action: InstallFinalize",
+            "Action ended 20:10:58: InstallFinalize. Return value 3.",
+            "[2023-12-10 23:18:08] Change number 21482018->21482152, apps: 0/113, packages: 0/7",
+            "[2023-12-10 23:18:09] Exception in CUtlBuffer",
+            "[1E78:1CCC][2023-09-03T16:46:28]e000: Error 0x80070643: Failed to install MSI package.",
+        ];
+
+        let mut settings = ParserSettings::default();
+        let mut parsed_result = parse_log_vec(&log_lines, "", &settings);
+        verify_parsed_result(&parsed_result, 7, 5);
+        assert!(parsed_result.iter().all(|entry| !entry.severity_inferred));
+        assert_eq!(parsed_result[6].severity, Some(Severity::Error));
+
+        settings.infer_levels = true;
+        apply_level_inference(&mut parsed_result, &settings);
+        let severities = parsed_result
+            .iter()
+            .map(|entry| entry.severity)
+            .collect::<Vec<Option<Severity>>>();
+        assert_eq!(
+            severities,
+            vec![
+                Some(Severity::Error),
+                Some(Severity::Error),
+                None,
+                Some(Severity::Error),
+                None,
+                Some(Severity::Error),
+                Some(Severity::Error),
+            ]
+        );
+        assert!(parsed_result[0].severity_inferred);
+        assert!(!parsed_result[6].severity_inferred); // explicit levels are kept as they are
+
+        settings.level_rules.insert(
+            "Steam".to_owned(),
+            vec![LevelRule::new("^Change number", Severity::Notice).unwrap()],
+        );
+        apply_level_inference(&mut parsed_result, &settings);
+        assert_eq!(parsed_result[4].severity, Some(Severity::Notice));
+
+        settings.infer_levels = false;
+        apply_level_inference(&mut parsed_result, &settings);
+        assert_eq!(parsed_result[0].severity, None);
+        assert_eq!(parsed_result[6].severity, Some(Severity::Error));
+    }
+
+    #[test]
+    fn test_session_detection() {
+        let log_lines = vec![
+            "1	[2023-12-26T06:41:43.537Z]	PID: 12196	TID: 13344	INFO    	(eax::Main) Starting",
+            "2	[2023-12-26T06:41:44.537Z]	PID: 12196	TID: 13344	INFO    	(eax::Main) Started",
+            "0	[2023-12-26T06:51:43.537Z]	PID: 13000	TID: 13344	INFO    	(eax::Main) Starting",
+            "1	[2023-12-26T06:51:44.537Z]	PID: 13000	TID: 13344	INFO    	(eax::Main) Started",
+            "[1E78:1CCC][2023-09-03T16:46:28]i001: Burn v3.8.1128.0, Windows v6.3 (Build 9600: Service Pack 0)",
+            "[1E78:1CCC][2023-09-03T16:46:28]i000: Setting string variable 'WixBundleLog'",
+            "[2023-12-10 23:18:08] Change number 21482018->21482152, apps: 0/113, packages: 0/7",
+            "[2023-12-10 23:33:48] Change number 21482152->21482258, apps: 0/76, packages: 0/20",
+            "[2023-12-23 13:44:05] Client version: 1702079146",
+        ];
+
+        let parsed_result = parse_log_vec(&log_lines, "", &ParserSettings::default());
+        verify_parsed_result(&parsed_result, 9, 5);
+        let sessions = parsed_result
+            .iter()
+            .map(|entry| entry.session)
+            .collect::<Vec<usize>>();
+        assert_eq!(sessions, vec![1, 1, 2, 2, 3, 3, 3, 3, 4]);
+        assert_eq!(parsed_result[8][LogEntryIndices::Session as usize], "4");
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01 00:00:01"), Some(1_000_000_000));
+        assert_eq!(
+            parse_timestamp("2023-12-26T06:41:43.537Z"),
+            Some(1_703_572_903_537_000_000)
+        );
+        assert_eq!(
+            parse_timestamp("2023.10.08-05.40.07:182"),
+            parse_timestamp("2023-10-08T05:40:07.182Z")
+        );
+        assert_eq!(
+            parse_timestamp("02:16:02 PM"),
+            Some((14 * 3600 + 16 * 60 + 2) * 1_000_000_000)
+        );
+        assert_eq!(parse_timestamp("20:10:58:904"), Some(72_658_904_000_000));
+        assert!(parse_timestamp("0901/211250.717") < parse_timestamp("1013/215308.845"));
+        assert_eq!(parse_timestamp("not a date"), None);
+    }
+
+    #[test]
+    fn test_time_zones() {
+        assert_eq!(Zone::from_text("UTC"), Some(Zone::Utc));
+        assert_eq!(Zone::from_text("local"), Some(Zone::Local));
+        assert_eq!(Zone::from_text("+05:30"), Some(Zone::Fixed(5 * 3600 + 30 * 60)));
+        assert_eq!(Zone::from_text("UTC-2"), Some(Zone::Fixed(-2 * 3600)));
+        assert_eq!(Zone::from_text("Europe/Paris"), None);
+        assert_eq!(Zone::Fixed(-9000).to_string(), "UTC-02:30");
+        assert_eq!(Zone::Fixed(3600).to_utc(3600 * 1_000_000_000), 0);
+        assert_eq!(Zone::Fixed(3600).to_zone_time(0), 3600 * 1_000_000_000);
+        // The local offset is the one in effect at each date
+        for timestamp in [1_688_000_000_000_000_000, 1_703_339_045_000_000_000] {
+            assert_eq!(Zone::Local.to_utc(Zone::Local.to_zone_time(timestamp)), timestamp);
+        }
+
+        let log_lines = vec![
+            "[2023-12-23 14:44:05] Steam started",
+            "2023-12-23T13:44:06.000Z info Node started",
+        ];
+
+        let settings = ParserSettings {
+            format_zones: [("Steam".to_owned(), Zone::Fixed(3600))].into(),
+            ..Default::default()
+        };
+        let mut parsed_result = parse_log_vec(&log_lines, "launcher.log", &settings);
+        verify_parsed_result(&parsed_result, 2, 5);
+        assert_eq!(parsed_result[0].timestamp, parse_timestamp("2023-12-23 13:44:05"));
+        assert_eq!(parsed_result[0].utc_offset, None);
+        assert_eq!(parsed_result[1].timestamp, parse_timestamp("2023-12-23 13:44:06"));
+        assert_eq!(parsed_result[1].utc_offset, Some(0));
+
+        // The zone of the tab overrides the one of the format but dates with an offset are kept
+        let settings = ParserSettings {
+            tab_zones: [("launcher.log".to_owned(), Zone::Utc)].into(),
+            ..settings
+        };
+        resolve_timestamps(&mut parsed_result, "launcher.log", &settings);
+        assert_eq!(parsed_result[0].timestamp, parse_timestamp("2023-12-23 14:44:05"));
+        assert_eq!(parsed_result[1].timestamp, parse_timestamp("2023-12-23 13:44:06"));
+
+        // Clock offsets apply to every entry and can be changed again
+        for clock_offset in [2_500_000_000, -1_000_000_000] {
+            let settings = ParserSettings {
+                clock_offsets: [("launcher.log".to_owned(), clock_offset)].into(),
+                ..settings.clone()
+            };
+            resolve_timestamps(&mut parsed_result, "launcher.log", &settings);
+            assert_eq!(
+                parsed_result[0].timestamp,
+                parse_timestamp("2023-12-23 14:44:05").map(|timestamp| timestamp + clock_offset)
+            );
+            assert_eq!(
+                parsed_result[1].timestamp,
+                parse_timestamp("2023-12-23 13:44:06").map(|timestamp| timestamp + clock_offset)
+            );
+        }
+    }
+
+    #[test]
+    fn test_order_timestamps() {
+        let entry = |date: &str| LogEntry {
+            columns: vec!["".to_owned(), "1".to_owned(), date.to_owned(), "".to_owned(), "".to_owned()],
+            ..Default::default()
+        };
+        let settings = ParserSettings {
+            tab_zones: [("app.log".to_owned(), Zone::Utc)].into(),
+            ..Default::default()
+        };
+
+        // Entries without a full date stay next to the dated entries around them
+        let mut entries = vec![
+            entry(""),
+            entry("2023-12-23 13:44:05"),
+            entry("13:44:06"),
+            entry(""),
+            entry("2023-12-23 13:44:07"),
+        ];
+        resolve_timestamps(&mut entries, "app.log", &settings);
+        let first = parse_timestamp("2023-12-23 13:44:05");
+        let order_timestamps = entries.iter().map(|entry| entry.order_timestamp).collect::<Vec<_>>();
+        assert_eq!(order_timestamps[..4], [first; 4]);
+        assert_eq!(order_timestamps[4], parse_timestamp("2023-12-23 13:44:07"));
+        assert_eq!(entries[2].timestamp, parse_timestamp("13:44:06"));
+
+        // Files without any full date don't go before the others
+        let mut entries = vec![entry("20:10:58:904"), entry("")];
+        resolve_timestamps(&mut entries, "app.log", &settings);
+        assert!(entries.iter().all(|entry| entry.order_timestamp.is_none()));
+    }
+
+    #[test]
+    fn test_severity_from_level_text() {
+        assert_eq!(Severity::from_level_text("ERROR"), Some(Severity::Error));
+        assert_eq!(Severity::from_level_text("error"), Some(Severity::Error));
+        assert_eq!(Severity::from_level_text("WARNING"), Some(Severity::Warn));
+        assert_eq!(Severity::from_level_text("WARN    "), Some(Severity::Warn));
+        assert_eq!(Severity::from_level_text("Info"), Some(Severity::Info));
+        assert_eq!(Severity::from_level_text("i"), Some(Severity::Info));
+        assert_eq!(Severity::from_level_text(""), None);
+        assert_eq!(Severity::from_level_text("LogInit"), None);
+        assert!(Severity::Fatal > Severity::Error && Severity::Warn > Severity::Notice);
     }
 }
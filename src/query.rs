@@ -0,0 +1,599 @@
+use anyhow::{anyhow, bail, Result};
+use regex::{Regex, RegexBuilder};
+
+use crate::matcher::{MatchMode, Matcher};
+use crate::parser::{parse_timestamp, LogEntry, LogEntryIndices, Severity};
+use crate::timezone::Zone;
+
+/// A filter parsed into a tree, e.g. `(error OR "timed out") AND NOT source:steam* AND level:>=warn`.
+///
+/// Words and quoted phrases are matched case-insensitively against the log column, `,` is a shorthand
+/// for `OR` (so the old comma-separated keywords still work) and terms next to each other are ANDed.
+pub enum Query {
+    /// Matches every entry (e.g. an empty filter)
+    All,
+    /// Keywords matched with one of the other [`MatchMode`]s
+    Keywords(Matcher),
+    Text(Regex),
+    /// A field predicate along with its term matched as text, for entries without such a field (e.g. `error:timeout`)
+    Field(FieldPredicate, Regex),
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn holds<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Equal => left == right,
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+        }
+    }
+}
+
+/// A `name:value` term, where the value can start with `>`, `>=`, `<`, `<=` or `=` to compare.
+pub enum FieldPredicate {
+    Level(Comparison, Severity),
+    Date(Comparison, i64),
+    Number(String, Comparison, f64),
+    /// Case-insensitive glob (`*` and `?`) matched against the whole value
+    Glob(String, Regex),
+}
+
+impl FieldPredicate {
+    fn new(name: &str, comparison: Comparison, value: &str, date_zone: Zone) -> Result<Self> {
+        let name = name.to_lowercase();
+        if name == "level" {
+            if let Some(severity) = Severity::from_level_text(value) {
+                return Ok(FieldPredicate::Level(comparison, severity));
+            }
+
+            if comparison != Comparison::Equal {
+                bail!("unknown level [{}]", value);
+            }
+        }
+
+        if name == "date" && comparison != Comparison::Equal {
+            let timestamp =
+                parse_date(value, date_zone).ok_or_else(|| anyhow!("invalid date [{}]", value))?;
+            return Ok(FieldPredicate::Date(comparison, timestamp));
+        }
+
+        if comparison != Comparison::Equal {
+            let number = value
+                .parse::<f64>()
+                .map_err(|_| anyhow!("[{}] can only be compared with a number", name))?;
+            return Ok(FieldPredicate::Number(name, comparison, number));
+        }
+
+        // The log and the date are long texts, so they match anywhere like the keywords do
+        let contains = ["log", "message", "msg", "date"].contains(&name.as_str());
+        let glob = regex::escape(value)
+            .replace(r"\*", ".*")
+            .replace(r"\?", ".");
+        let pattern = if contains {
+            glob
+        } else {
+            format!("^{}$", glob)
+        };
+        let regex = RegexBuilder::new(&pattern).case_insensitive(true).build()?;
+        Ok(FieldPredicate::Glob(name, regex))
+    }
+
+    /// Whether the entry matches, `None` when the entry has no such field.
+    fn matches(&self, entry: &LogEntry) -> Option<bool> {
+        match self {
+            FieldPredicate::Level(comparison, severity) => Some(
+                entry
+                    .severity
+                    .is_some_and(|entry_severity| comparison.holds(entry_severity, *severity)),
+            ),
+            FieldPredicate::Date(comparison, timestamp) => Some(
+                entry
+                    .timestamp
+                    .is_some_and(|entry_timestamp| comparison.holds(entry_timestamp, *timestamp)),
+            ),
+            FieldPredicate::Number(name, comparison, number) => {
+                field_value(entry, name).map(|value| {
+                    value
+                        .trim()
+                        .parse::<f64>()
+                        .is_ok_and(|value| comparison.holds(value, *number))
+                })
+            }
+            FieldPredicate::Glob(name, regex) => {
+                field_value(entry, name).map(|value| regex.is_match(value))
+            }
+        }
+    }
+}
+
+/// Value of a column (by its header name) or of one of the extra fields of the entry.
+fn field_value<'a>(entry: &'a LogEntry, name: &str) -> Option<&'a str> {
+    let column = match name {
+        "source" | "file" => LogEntryIndices::FileName,
+        "session" => LogEntryIndices::Session,
+        "date" => LogEntryIndices::Date,
+        "level" => LogEntryIndices::Level,
+        "log" | "message" | "msg" => LogEntryIndices::Log,
+        "format" => return Some(entry.format),
+        _ => {
+            return entry
+                .fields
+                .iter()
+                .find(|(field_name, _)| field_name.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    };
+
+    Some(&entry[column as usize])
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    LeftParenthesis,
+    RightParenthesis,
+    Or,
+    And,
+    Not,
+    Word(String),
+    Phrase(String),
+}
+
+/// Splits the query into tokens along with the (character) position they start at.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().enumerate().peekable();
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            ',' => Token::Or,
+            '!' if chars.peek().is_some_and(|(_, next)| *next != '=') => Token::Not,
+            _ => {
+                // A word, which can contain quoted parts (e.g. `source:"Steam log*"`)
+                let mut word = String::new();
+                let mut quoted = false;
+                let phrase = c == '"';
+                let mut next = Some((position, c));
+                while let Some((position, c)) = next {
+                    match c {
+                        '"' => {
+                            quoted = true;
+                            loop {
+                                match chars.next() {
+                                    Some((_, '\\'))
+                                        if chars.peek().is_some_and(|(_, c)| *c == '"') =>
+                                    {
+                                        word.push('"');
+                                        chars.next();
+                                    }
+                                    Some((_, '"')) => break,
+                                    Some((_, c)) => word.push(c),
+                                    None => bail!(
+                                        "missing closing quote for the one at {}",
+                                        position + 1
+                                    ),
+                                }
+                            }
+                        }
+                        _ => word.push(c),
+                    }
+
+                    next = chars.next_if(|(_, c)| !c.is_whitespace() && !"(),".contains(*c));
+                }
+
+                match word.as_str() {
+                    _ if phrase => Token::Phrase(word),
+                    _ if quoted => Token::Word(word),
+                    "AND" | "&&" => Token::And,
+                    "OR" | "||" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Word(word),
+                }
+            }
+        };
+
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+struct QueryParser {
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+    date_zone: Zone,
+}
+
+impl QueryParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.index).map_or_else(
+            || self.tokens.last().map_or(0, |(position, _)| *position + 1),
+            |(position, _)| *position,
+        ) + 1
+    }
+
+    fn or(&mut self) -> Result<Query> {
+        let mut queries = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.index += 1;
+            queries.push(self.and()?);
+        }
+
+        Ok(if queries.len() == 1 {
+            queries.remove(0)
+        } else {
+            Query::Or(queries)
+        })
+    }
+
+    fn and(&mut self) -> Result<Query> {
+        let mut queries = vec![self.not()?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => self.index += 1,
+                // Terms next to each other are ANDed
+                Some(Token::Not | Token::LeftParenthesis | Token::Word(_) | Token::Phrase(_)) => {}
+                _ => break,
+            }
+            queries.push(self.not()?);
+        }
+
+        Ok(if queries.len() == 1 {
+            queries.remove(0)
+        } else {
+            Query::And(queries)
+        })
+    }
+
+    fn not(&mut self) -> Result<Query> {
+        if self.peek() == Some(&Token::Not) {
+            self.index += 1;
+            return Ok(Query::Not(Box::new(self.not()?)));
+        }
+
+        self.term()
+    }
+
+    fn term(&mut self) -> Result<Query> {
+        let position = self.position();
+        let Some((_, token)) = self.tokens.get(self.index) else {
+            bail!("expected a term at {}", position);
+        };
+        self.index += 1;
+
+        match token {
+            Token::LeftParenthesis => {
+                let query = self.or()?;
+                if self.peek() != Some(&Token::RightParenthesis) {
+                    bail!("missing ')' for the '(' at {}", position);
+                }
+                self.index += 1;
+                Ok(query)
+            }
+            Token::Phrase(phrase) => text_query(phrase),
+            Token::Word(word) => match FIELD_PREDICATE_REGEX.captures(word) {
+                // URLs look like `name:value` too, and a word ending with a colon is just text (e.g. `connect:`)
+                Some(captures)
+                    if !captures["value"].is_empty() && !captures["value"].starts_with("//") =>
+                {
+                    let comparison = match captures.name("comparison").map_or("", |m| m.as_str()) {
+                        "<" => Comparison::Less,
+                        "<=" => Comparison::LessOrEqual,
+                        ">" => Comparison::Greater,
+                        ">=" => Comparison::GreaterOrEqual,
+                        _ => Comparison::Equal,
+                    };
+                    FieldPredicate::new(
+                        &captures["name"],
+                        comparison,
+                        &captures["value"],
+                        self.date_zone,
+                    )
+                    .and_then(|predicate| Ok(Query::Field(predicate, text_regex(word)?)))
+                    .map_err(|e| anyhow!("{} at {}", e, position))
+                }
+                _ => text_query(word),
+            },
+            token => bail!("unexpected {} at {}", token_text(token), position),
+        }
+    }
+}
+
+fn text_query(text: &str) -> Result<Query> {
+    Ok(Query::Text(text_regex(text)?))
+}
+
+fn text_regex(text: &str) -> Result<Regex> {
+    Ok(RegexBuilder::new(&regex::escape(text))
+        .case_insensitive(true)
+        .build()?)
+}
+
+fn token_text(token: &Token) -> &str {
+    match token {
+        Token::LeftParenthesis => "'('",
+        Token::RightParenthesis => "')'",
+        Token::Or => "OR",
+        Token::And => "AND",
+        Token::Not => "NOT",
+        Token::Word(word) | Token::Phrase(word) => word,
+    }
+}
+
+/// Reads a date typed by the user (e.g. `2023-12-23T13:00`, `2023-12-23` or `13:00:05`) in `zone`, in UTC nanoseconds.
+///
+/// Dates are typed the way they are shown, so `zone` is the display zone.
+pub fn parse_date(text: &str, zone: Zone) -> Option<i64> {
+    let text = text.trim();
+    let timestamp = [
+        text.to_owned(),
+        format!("{}:00", text),
+        format!("{} 00:00:00", text),
+    ]
+    .iter()
+    .find_map(|date| parse_timestamp(date))?;
+    Some(zone.to_utc(timestamp))
+}
+
+/// Reads a duration such as `30s`, `500ms`, `5m`, `2h` or `1d` (seconds without a unit), in nanoseconds.
+pub fn parse_duration(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let unit_start = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(unit_start);
+    let nanoseconds_per_unit = match unit.trim() {
+        "ms" => 1_000_000.0,
+        "" | "s" => 1_000_000_000.0,
+        "m" | "min" => 60_000_000_000.0,
+        "h" => 3_600_000_000_000.0,
+        "d" => 86_400_000_000_000.0,
+        _ => return None,
+    };
+    let number = number.parse::<f64>().ok()?;
+    Some((number * nanoseconds_per_unit).round() as i64)
+}
+
+/// Query matching log messages like the given one, i.e. with any numbers, ids or hashes in it.
+pub fn like_query(message: &str) -> String {
+    const MAX_LENGTH: usize = 100;
+
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    let line = line.chars().take(MAX_LENGTH).collect::<String>();
+    let pattern = VARIABLE_PART_REGEX.replace_all(line.trim_end_matches('\\'), "*");
+    let pattern = REPEATED_WILDCARD_REGEX.replace_all(&pattern, "*");
+    format!("log:\"{}\"", pattern.replace('"', "\\\""))
+}
+
+lazy_static! {
+    static ref VARIABLE_PART_REGEX: Regex =
+        Regex::new(r#"[0-9A-Fa-fx.:_-]*\d[0-9A-Fa-fx.:_-]*"#).unwrap();
+    static ref REPEATED_WILDCARD_REGEX: Regex = Regex::new(r#"\*(?:\s*\*)+"#).unwrap();
+    static ref FIELD_PREDICATE_REGEX: Regex =
+        Regex::new(r#"^(?P<name>[A-Za-z_][\w.]*):(?P<comparison>>=|<=|>|<|=)?(?P<value>.*)$"#)
+            .unwrap();
+}
+
+impl Query {
+    /// Parses the filter input, which is only a query in [`MatchMode::Query`] (the other modes match keywords).
+    ///
+    /// Dates in the query are read in `date_zone`.
+    pub fn new(input: &str, mode: MatchMode, date_zone: Zone) -> Result<Query> {
+        if mode != MatchMode::Query {
+            return Ok(Query::Keywords(Matcher::new(input, mode)?));
+        }
+
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Ok(Query::All);
+        }
+
+        let mut parser = QueryParser {
+            tokens,
+            index: 0,
+            date_zone,
+        };
+        let query = parser.or()?;
+        if let Some((position, token)) = parser.tokens.get(parser.index) {
+            bail!("unexpected {} at {}", token_text(token), position + 1);
+        }
+
+        Ok(query)
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        match self {
+            Query::All => true,
+            Query::Keywords(matcher) => {
+                matcher.is_empty() || matcher.is_match(&entry[LogEntryIndices::Log as usize])
+            }
+            Query::Text(regex) => regex.is_match(&entry[LogEntryIndices::Log as usize]),
+            Query::Field(predicate, text) => predicate
+                .matches(entry)
+                .unwrap_or_else(|| text.is_match(&entry[LogEntryIndices::Log as usize])),
+            Query::Not(query) => !query.matches(entry),
+            Query::And(queries) => queries.iter().all(|query| query.matches(entry)),
+            Query::Or(queries) => queries.iter().any(|query| query.matches(entry)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::MatchMode;
+    use crate::parser::{parse_timestamp, LogEntry, Severity};
+    use crate::query::{like_query, parse_date, parse_duration, Query};
+    use crate::timezone::Zone;
+
+    fn entry(
+        source: &str,
+        date: &str,
+        level: &str,
+        log: &str,
+        fields: &[(&str, &str)],
+    ) -> LogEntry {
+        LogEntry {
+            columns: vec![
+                source.to_string(),
+                "1".to_string(),
+                date.to_string(),
+                level.to_string(),
+                log.to_string(),
+            ],
+            severity: Severity::from_level_text(level),
+            format: "Steam",
+            session: 1,
+            timestamp: parse_timestamp(date),
+            fields: fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_query_parse() {
+        let entries = [
+            entry(
+                "steam_bootstrap.log",
+                "2023-12-23 12:59:58",
+                "Info",
+                "Startup - updater built Dec 18 2023",
+                &[("tid", "8300")],
+            ),
+            entry(
+                "steam_bootstrap.log",
+                "2023-12-23 13:00:05",
+                "Warn",
+                "Connection timed out, retrying",
+                &[("tid", "8301")],
+            ),
+            entry(
+                "EpicGamesLauncher.log",
+                "2023-12-23 13:10:00",
+                "Error",
+                "Failed to connect: timed out",
+                &[("status", "503")],
+            ),
+        ];
+        let matching = |input: &str| {
+            let query = Query::new(input, MatchMode::Query, Zone::Utc).unwrap();
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| query.matches(entry))
+                .map(|(index, _)| index)
+                .collect::<Vec<usize>>()
+        };
+
+        assert_eq!(matching(""), [0, 1, 2]);
+        assert_eq!(matching("startup,FAILED"), [0, 2]);
+        assert_eq!(matching("timed out"), [1, 2]);
+        assert_eq!(matching("\"out, retrying\""), [1]);
+        assert_eq!(matching("level:>=warn AND NOT source:steam*"), [2]);
+        assert_eq!(matching("(startup OR failed) !source:epic*"), [0]);
+        assert_eq!(matching("tid:8300 || status:>=500"), [0, 2]);
+        assert_eq!(matching("date:>2023-12-23T13:00"), [1, 2]);
+        assert_eq!(matching("date:<2023-12-23T13:00:05 OR level:error"), [0, 2]);
+        assert_eq!(matching("date:12-23 format:steam"), [0, 1, 2]);
+        assert_eq!(matching("http://example.com"), Vec::<usize>::new());
+        // Entries without such a field match the term as text
+        assert_eq!(matching("connect:"), [2]);
+        assert_eq!(matching("Connect: timed"), [2]);
+        assert_eq!(matching("tid:8301 OR failed"), [1, 2]);
+        assert_eq!(matching("status:503"), [2]);
+
+        for invalid in [
+            "(startup",
+            "startup)",
+            "NOT",
+            "level:>=loud",
+            "tid:>high",
+            "\"open",
+        ] {
+            assert!(
+                Query::new(invalid, MatchMode::Query, Zone::Utc).is_err(),
+                "{}",
+                invalid
+            );
+        }
+        assert!(Query::new("(startup", MatchMode::Plain, Zone::Utc).is_ok());
+    }
+
+    #[test]
+    fn test_parse_date_and_duration() {
+        let timestamp = parse_timestamp("2023-12-23 13:00:00").unwrap();
+        assert_eq!(parse_date("2023-12-23T13:00", Zone::Utc), Some(timestamp));
+        assert_eq!(
+            parse_date("2023-12-23 14:00:00", Zone::Fixed(3600)),
+            Some(timestamp)
+        );
+        assert_eq!(
+            parse_date("2023-12-23", Zone::Utc),
+            Some(timestamp - 13 * 3_600_000_000_000)
+        );
+        assert_eq!(parse_date("yesterday", Zone::Utc), None);
+
+        assert_eq!(parse_duration("30s"), Some(30_000_000_000));
+        assert_eq!(parse_duration("500ms"), Some(500_000_000));
+        assert_eq!(parse_duration("1.5m"), Some(90_000_000_000));
+        assert_eq!(parse_duration("2"), Some(2_000_000_000));
+        assert_eq!(parse_duration("5 weeks"), None);
+        assert_eq!(parse_duration("s"), None);
+    }
+
+    #[test]
+    fn test_like_query() {
+        let query =
+            like_query("Connection to 10.0.0.12:8080 timed out after 250ms (attempt 2)\nat main");
+        assert_eq!(
+            query,
+            r#"log:"Connection to * timed out after *ms (attempt *)""#
+        );
+
+        let query = Query::new(&query, MatchMode::Query, Zone::Utc).unwrap();
+        let entry = |log: &str| LogEntry {
+            columns: vec![
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                log.to_string(),
+            ],
+            ..Default::default()
+        };
+        assert!(query.matches(&entry(
+            "Connection to 192.168.1.1:443 timed out after 3000ms (attempt 10)"
+        )));
+        assert!(!query.matches(&entry("Connection to 192.168.1.1:443 refused")));
+
+        assert_eq!(
+            like_query(r#"Loaded "config.json" in 3 ms\"#),
+            r#"log:"Loaded \"config.json\" in * ms""#
+        );
+    }
+}
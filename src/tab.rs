@@ -1,39 +1,139 @@
-use crate::parser::LogEntry;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
-#[derive(Clone)]
+use crate::parser::{LogEntry, Severity};
+use crate::thirdparty::input::Input;
+
+/// How a row of a view is shown when filtering with context lines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RowKind {
+    #[default]
+    Match,
+    /// Shown (dimmed) because it is close to a match
+    Context,
+    /// Not an entry of the file but a marker for the entries hidden from the row's entry to `last_line`
+    Separator { hidden: usize, last_line: usize },
+}
+
+/// Row of a view, pointing at an entry of the [`EntryStore`] rather than holding a copy of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewRow {
+    /// Id of the tab of the file the entry comes from
+    pub tab_id: usize,
+    /// Index of the entry in its file (the first hidden one for separators)
+    pub index: usize,
+    pub kind: RowKind,
+}
+
+#[derive(Clone, Default)]
 pub struct TableItems {
-    pub data: Vec<LogEntry>,
+    /// Shared so that background jobs can go through the rows without copying them
+    pub data: Arc<Vec<ViewRow>>,
     pub selected_item_index: usize,
 }
 
+impl TableItems {
+    /// Every entry of a file, as matches.
+    pub fn all(tab_id: usize, len: usize) -> TableItems {
+        TableItems {
+            data: Arc::new(
+                (0..len)
+                    .map(|index| ViewRow {
+                        tab_id,
+                        index,
+                        kind: RowKind::Match,
+                    })
+                    .collect(),
+            ),
+            selected_item_index: 0,
+        }
+    }
+}
+
+/// Entries of every open file keyed by the id of their tab, the only place entries are kept.
+#[derive(Clone, Default)]
+pub struct EntryStore {
+    entries: HashMap<usize, Arc<Vec<LogEntry>>>,
+}
+
+impl EntryStore {
+    /// Entries of the file of a tab (none for the combined tab).
+    pub fn get(&self, tab_id: usize) -> &[LogEntry] {
+        self.entries.get(&tab_id).map_or(&[], |entries| entries.as_slice())
+    }
+
+    /// Entries of the file of a tab, shared with a background job.
+    pub fn shared(&self, tab_id: usize) -> Arc<Vec<LogEntry>> {
+        self.entries.get(&tab_id).cloned().unwrap_or_default()
+    }
+
+    /// Entries of the file of a tab to change. Jobs going through them should be cancelled first (see
+    /// [`Job::cancel`](crate::job::Job::cancel)), otherwise they're copied.
+    pub fn get_mut(&mut self, tab_id: usize) -> &mut Vec<LogEntry> {
+        Arc::make_mut(self.entries.entry(tab_id).or_default())
+    }
+
+    pub fn insert(&mut self, tab_id: usize, entries: Vec<LogEntry>) {
+        self.entries.insert(tab_id, Arc::new(entries));
+    }
+
+    pub fn remove(&mut self, tab_id: usize) {
+        self.entries.remove(&tab_id);
+    }
+
+    /// Entry a row points at. Rows never outlive the entries of their tab (closing a tab or replacing its
+    /// entries drops its rows first), so a row of a tab without entries is a bug.
+    pub fn entry(&self, row: &ViewRow) -> &LogEntry {
+        &self.entries[&row.tab_id][row.index]
+    }
+}
+
 pub enum TabType {
     Normal,
     Combined, // the tab which combines data from all other tabs
 }
 
+/// Tab ids never repeat, so that views can't point at the entries of a tab closed since
+static NEXT_TAB_ID: AtomicUsize = AtomicUsize::new(0);
+
 pub struct Tab {
+    pub id: usize,
     pub name: String,
     pub file_path: String,
-    items: TableItems,
     pub filtered_view_items: TableItems,
     pub last_file_size: usize,
     pub tab_type: TabType,
+    /// Filter and search of this tab, used when they aren't linked across tabs
+    pub filter_input_text: Input,
+    pub search_input_text: Input,
+    /// Entries of each level kept by the filters (before the levels are filtered), `None` counting the ones without a level
+    pub level_counts: HashMap<Option<Severity>, usize>,
+    /// Lines of the hidden entries shown by expanding a separator, as if they were context
+    pub expanded_lines: HashSet<usize>,
 }
 
 impl Tab {
-    pub fn new(file_path: String, table_items: TableItems, tab_type: TabType) -> Self {
+    /// Creates a tab with an empty view, its entries going in the [`EntryStore`] under its `id`.
+    pub fn new(file_path: String, tab_type: TabType) -> Self {
+        let id = NEXT_TAB_ID.fetch_add(1, Ordering::Relaxed);
         if let TabType::Combined = tab_type {
             return Tab {
+                id,
                 name: " [Combined] ".to_owned(),
-                items: table_items.clone(),
-                filtered_view_items: table_items,
+                filtered_view_items: TableItems::default(),
                 last_file_size: 0,
                 file_path: "".to_owned(),
                 tab_type,
+                filter_input_text: Input::default(),
+                search_input_text: Input::default(),
+                level_counts: HashMap::new(),
+                expanded_lines: HashSet::new(),
             };
         }
 
         Tab {
+            id,
             name: format!(
                 " [{}] ",
                 std::path::Path::new(file_path.clone().as_str())
@@ -42,8 +142,7 @@ impl Tab {
                     .to_str()
                     .unwrap()
             ),
-            items: table_items.clone(),
-            filtered_view_items: table_items,
+            filtered_view_items: TableItems::default(),
             last_file_size: if let Ok(meta) = std::fs::metadata(file_path.clone()) {
                 meta.len().try_into().unwrap_or(0)
             } else {
@@ -51,14 +150,10 @@ impl Tab {
             },
             file_path: file_path.to_string(),
             tab_type,
+            filter_input_text: Input::default(),
+            search_input_text: Input::default(),
+            level_counts: HashMap::new(),
+            expanded_lines: HashSet::new(),
         }
     }
-
-    pub fn items_mut(&mut self) -> &mut TableItems {
-        &mut self.items
-    }
-
-    pub fn items(&self) -> &TableItems {
-        &self.items
-    }
 }
@@ -0,0 +1,115 @@
+use std::fmt;
+
+use chrono::{DateTime, Local, Offset, TimeZone};
+
+/// Time zone that dates without an explicit UTC offset were written in, or that dates are shown in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Zone {
+    Utc,
+    /// Time zone of this machine
+    Local,
+    /// Fixed number of seconds east of UTC (e.g. `+01:00`)
+    Fixed(i64),
+}
+
+impl Zone {
+    /// Reads the zone names used in the config file: `utc`, `local` or an offset such as `+01:00`, `-0500` or `UTC+2`.
+    pub fn from_text(text: &str) -> Option<Zone> {
+        let text = text.trim().to_lowercase();
+        match text.as_str() {
+            "utc" | "z" | "gmt" => return Some(Zone::Utc),
+            "local" => return Some(Zone::Local),
+            _ => {}
+        }
+
+        let offset = text
+            .strip_prefix("utc")
+            .or_else(|| text.strip_prefix("gmt"))
+            .unwrap_or(&text);
+        parse_utc_offset(offset).map(Zone::Fixed)
+    }
+
+    /// Converts a date written in this zone (in nanoseconds, read as if it were UTC) into UTC nanoseconds.
+    pub fn to_utc(self, timestamp: i64) -> i64 {
+        let offset = match self {
+            Zone::Utc => 0,
+            Zone::Local => local_offset_of_local_time(timestamp),
+            Zone::Fixed(offset) => offset,
+        };
+        timestamp.saturating_sub(offset * 1_000_000_000)
+    }
+
+    /// Converts UTC nanoseconds into the date shown in this zone (in nanoseconds, to be formatted as if it were UTC).
+    pub fn to_zone_time(self, timestamp: i64) -> i64 {
+        let offset = match self {
+            Zone::Utc => 0,
+            Zone::Local => local_offset_at(timestamp),
+            Zone::Fixed(offset) => offset,
+        };
+        timestamp.saturating_add(offset * 1_000_000_000)
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Zone::Utc => write!(f, "UTC"),
+            Zone::Local => write!(f, "local"),
+            Zone::Fixed(offset) => write!(
+                f,
+                "UTC{}{:02}:{:02}",
+                if *offset < 0 { '-' } else { '+' },
+                offset.abs() / 3600,
+                offset.abs() / 60 % 60
+            ),
+        }
+    }
+}
+
+/// Reads a UTC offset written as `+01:00`, `+0100`, `+01` or `+1`, in seconds east of UTC.
+pub fn parse_utc_offset(text: &str) -> Option<i64> {
+    let sign = match text.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+
+    let digits = text[1..].replace(':', "");
+    if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes) = if digits.len() > 2 {
+        digits.split_at(digits.len() - 2)
+    } else {
+        (digits.as_str(), "0")
+    };
+    let hours = hours.parse::<i64>().ok()?;
+    let minutes = minutes.parse::<i64>().ok()?;
+    (hours <= 14 && minutes < 60).then_some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Seconds east of UTC of this machine's time zone at the UTC time, which changes with daylight saving time.
+fn local_offset_at(timestamp: i64) -> i64 {
+    Local
+        .timestamp_opt(timestamp.div_euclid(1_000_000_000), 0)
+        .earliest()
+        .map_or(0, |date| date.offset().fix().local_minus_utc() as i64)
+}
+
+/// Seconds east of UTC of this machine's time zone at the local time.
+///
+/// Times repeated when the clocks go back use the first offset, and times skipped when they go forward (which
+/// can't be local times) use the offset of the day before.
+fn local_offset_of_local_time(timestamp: i64) -> i64 {
+    let Some(date) = DateTime::from_timestamp(timestamp.div_euclid(1_000_000_000), 0) else {
+        return 0;
+    };
+    Local
+        .from_local_datetime(&date.naive_utc())
+        .earliest()
+        .map_or_else(
+            || local_offset_at(timestamp.saturating_sub(24 * 3600 * 1_000_000_000)),
+            |date| date.offset().fix().local_minus_utc() as i64,
+        )
+}
@@ -1,6 +1,12 @@
 use crate::tab::TabType;
+use crate::matcher::{MatchMode, Matcher};
+use crate::parser::{format_timestamp, Severity, DELIMITED_FORMAT};
+use crate::tab::RowKind;
+use crate::app::LEVEL_FILTERS;
+use crate::timezone::Zone;
 use crate::{app::SelectedInput, parser::LogEntryIndices, App, ViewMode};
-use ratatui::layout::Margin;
+use ratatui::layout::{Margin, Rect};
+use ratatui::text::{Line, Span};
 use ratatui::style::Stylize;
 use ratatui::widgets::block::Position;
 use ratatui::widgets::Scrollbar;
@@ -10,7 +16,7 @@ use ratatui::{
     layout::{Constraint, Layout},
     prelude::Direction,
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
 
@@ -18,8 +24,236 @@ use crate::utils::{beatify_enclosed_json, highlight_keywords_in_text};
 
 const DEFAULT_BG_COLOR: Color = Color::DarkGray;
 const DEFAULT_HIGHLIGHT_COLOR: Color = Color::LightMagenta;
+const MAX_FIELD_COLUMN_WIDTH: usize = 20;
+const MAX_FILTER_RULES_PANEL_HEIGHT: usize = 12;
+/// Characters kept before the first match of a search result snippet
+const SNIPPET_LEADING_CHARS: usize = 20;
+
+fn severity_style(severity: Option<Severity>, inferred: bool) -> Style {
+    let style = Style::default().bg(DEFAULT_BG_COLOR).fg(Color::White);
+    if inferred {
+        // Inferred levels only colour the text so they stand out less than the explicit ones
+        return match severity {
+            Some(Severity::Fatal) | Some(Severity::Error) => style.fg(Color::LightRed),
+            Some(Severity::Warn) => style.fg(Color::LightYellow),
+            _ => style,
+        };
+    }
+
+    match severity {
+        Some(Severity::Fatal) => style
+            .bg(Color::Red)
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+        Some(Severity::Error) => style.bg(Color::Red).fg(Color::White),
+        Some(Severity::Warn) => style.bg(Color::LightYellow).fg(Color::Black),
+        Some(Severity::Trace) | Some(Severity::Debug) => style.fg(Color::Gray),
+        _ => style,
+    }
+}
+
+/// Border of the filter/search inputs showing their match mode and why the input can't be used.
+fn input_block(title: &str, mode: MatchMode, error: Option<anyhow::Error>) -> Block<'static> {
+    let mut title = vec![Span::raw(format!("{} ({}) [<C-r>: mode]", title, mode))];
+    if let Some(error) = error {
+        // Regex errors end with a short description after the pattern and a caret pointing at the problem
+        let message = error.to_string();
+        title.push(Span::styled(
+            format!(" {} ", message.lines().last().unwrap_or_default()),
+            Style::default().fg(Color::White).bg(Color::Red),
+        ));
+    }
+
+    Block::default().borders(Borders::ALL).title(Line::from(title))
+}
+
+/// Ordered include/exclude rules applied after the filter input, with how many entries each one matched.
+fn render_filter_rules(f: &mut Frame, app: &App, area: Rect) {
+    let rows = app.filter_rules().iter().map(|rule| {
+        let style = if rule.enabled {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::Gray).add_modifier(Modifier::CROSSED_OUT)
+        };
+        Row::new([
+            Cell::from(if rule.enabled { "[x]" } else { "[ ]" }),
+            Cell::from(if rule.exclude { "exclude" } else { "include" }),
+            match rule.match_count {
+                Some(count) => Cell::from(count.to_string()),
+                None => Cell::from("invalid").fg(Color::LightRed),
+            },
+            Cell::from(rule.query.clone()),
+        ])
+        .style(style)
+    });
+    let header =
+        Row::new(["on", "rule", "matches", "query"]).style(Style::default().fg(Color::White));
+
+    let mut state = TableState::default();
+    if !app.filter_rules().is_empty() {
+        state.select(Some(app.selected_filter_rule_index()));
+    }
+
+    let t = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Percentage(100),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" [Filter rules] [a]dd filter input, [space] on/off, [i]nclude/exclude, [K]/[J] move, [d]elete "),
+    )
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    .bg(DEFAULT_BG_COLOR);
+
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(t, area, &mut state);
+}
+
+/// Text shown in the log column of a separator row.
+fn separator_text(hidden: usize) -> String {
+    format!("··· {} hidden entries [Enter] to show more ···", hidden)
+}
+
+/// Line of `text` with the first search match, starting shortly before the match.
+fn search_snippet(text: &str, matcher: Option<&Matcher>) -> String {
+    let match_start = matcher
+        .and_then(|matcher| matcher.find_ranges(text).first().map(|range| range.start))
+        .unwrap_or_default();
+    let line_start = text[..match_start].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = text[match_start..]
+        .find('\n')
+        .map_or(text.len(), |index| match_start + index);
+    let leading = text[line_start..match_start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_LEADING_CHARS - 1)
+        .map_or(line_start, |(index, _)| line_start + index);
+    let prefix = if leading > line_start { "…" } else { "" };
+    format!("{}{}", prefix, text[leading..line_end].trim_end())
+}
+
+/// Every search match of the current tab (or of all tabs), only building the rows that fit in the pane.
+fn render_search_results(f: &mut Frame, app: &App, area: Rect) {
+    let search_matcher = app.search_matcher().ok();
+    let matches = app
+        .search_results()
+        .map_or(&[][..], |results| results.matches.as_slice());
+    let visible_rows = area.height.saturating_sub(2) as usize;
+    // The matches may still be coming in
+    let selected = app
+        .selected_search_result_index()
+        .min(matches.len().saturating_sub(1));
+    let first = selected
+        .saturating_sub(visible_rows / 2)
+        .min(matches.len().saturating_sub(visible_rows));
+
+    let results = matches
+        .iter()
+        .skip(first)
+        .take(visible_rows)
+        .map(|&(tab_index, item_index)| {
+            let item = app.entry(&app.tabs()[tab_index].filtered_view_items.data[item_index]);
+            let date = match (app.display_zone(), item.timestamp) {
+                (Some(zone), Some(timestamp)) => format_timestamp(zone.to_zone_time(timestamp)),
+                _ => item[LogEntryIndices::Date as usize].clone(),
+            };
+            let snippet = search_snippet(&item[LogEntryIndices::Log as usize], search_matcher.as_ref());
+            (item, date, snippet)
+        })
+        .collect::<Vec<_>>();
+    let rows = results.iter().map(|(item, date, snippet)| {
+        Row::new([
+            Cell::from(item[LogEntryIndices::FileName as usize].as_str()),
+            Cell::from(date.as_str()),
+            Cell::from(highlight_keywords_in_text(snippet, search_matcher.as_ref())),
+        ])
+        .style(severity_style(item.severity, item.severity_inferred))
+    });
+
+    let mut state = TableState::default();
+    if !matches.is_empty() {
+        state.select(Some(selected - first));
+    }
+
+    let scope = if app.search_results_all_tabs() {
+        "all tabs"
+    } else {
+        "this tab"
+    };
+    let searching = match app.search_results() {
+        Some(results) if !results.complete => " (searching...)",
+        _ => "",
+    };
+    let t = Table::new(
+        rows,
+        [
+            Constraint::Percentage(15),
+            Constraint::Length(26),
+            Constraint::Percentage(85),
+        ],
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        " [Search results] {} matches in {}{}, [a]ll tabs / this tab, [Enter] jump ",
+        matches.len(),
+        scope,
+        searching
+    )))
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    .bg(DEFAULT_BG_COLOR);
+
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(t, area, &mut state);
+}
+
+/// Chips above the table showing the entries of each level in the current tab, hidden levels crossed out.
+fn render_level_chips(f: &mut Frame, app: &mut App, area: Rect) {
+    let level_counts = &app.tabs()[app.selected_tab_index()].level_counts;
+    let mut spans = vec![];
+    let mut chip_areas = vec![];
+    let mut x = area.x;
+    for (index, level) in LEVEL_FILTERS.into_iter().enumerate() {
+        let name = level.map_or("NONE".to_owned(), |severity| format!("{:?}", severity).to_uppercase());
+        let chip = format!(
+            " {} {} {} ",
+            index + 1,
+            name,
+            level_counts.get(&level).copied().unwrap_or_default()
+        );
+        let width = chip.chars().count() as u16;
+        if x + width > area.right() {
+            break;
+        }
+
+        let style = if app.level_hidden(level) {
+            Style::default()
+                .fg(Color::Gray)
+                .bg(DEFAULT_BG_COLOR)
+                .add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            severity_style(level, false).add_modifier(Modifier::BOLD)
+        };
+        spans.push(Span::styled(chip, style));
+        spans.push(Span::raw(" "));
+        chip_areas.push((Rect::new(x, area.y, width, 1), level));
+        x += width + 1;
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)).bg(DEFAULT_BG_COLOR), area);
+    *app.level_chip_areas_mut() = chip_areas;
+}
 
 pub fn render(f: &mut Frame, app: &mut App) {
+    // Only clickable while they're shown
+    app.level_chip_areas_mut().clear();
+    app.update_search_results();
+
     let is_in_table_item_mode = matches!(app.view_mode().back(), Some(ViewMode::TableItem(_)));
 
     let areas = Layout::default()
@@ -44,6 +278,8 @@ pub fn render(f: &mut Frame, app: &mut App) {
         const FILTER_PREFIX: &str = "[f]ilter";
         const SEARCH_PREFIX: &str = "[s]earch";
         const COPY_PREFIX: &str = "[c]opy";
+        const INFER_LEVELS_PREFIX: &str = "[i]nfer levels";
+        const FILTER_RULES_PREFIX: &str = "[F]ilter rules";
         let menu = [
             "[o]pen",
             &(TAIL_PREFIX.to_owned()
@@ -54,7 +290,9 @@ pub fn render(f: &mut Frame, app: &mut App) {
                 }),
             SEARCH_PREFIX,
             FILTER_PREFIX,
+            FILTER_RULES_PREFIX,
             COPY_PREFIX,
+            INFER_LEVELS_PREFIX,
             "move [Arrow keys]",
             "select [enter]",
             "[b]ack [Esc]",
@@ -85,6 +323,9 @@ pub fn render(f: &mut Frame, app: &mut App) {
                     && (!app.filter_input_text().to_string().is_empty()))
                 || (menu[i].starts_with(SEARCH_PREFIX) && search_focused)
                 || (menu[i].starts_with(COPY_PREFIX) && app.copying_to_clipboard())
+                || (menu[i].starts_with(INFER_LEVELS_PREFIX) && app.infer_levels())
+                || (menu[i].starts_with(FILTER_RULES_PREFIX)
+                    && app.filter_rules().iter().any(|rule| rule.enabled))
             {
                 menu_item = menu_item.bg(DEFAULT_HIGHLIGHT_COLOR);
             }
@@ -114,8 +355,11 @@ pub fn render(f: &mut Frame, app: &mut App) {
             log_text = json_beautified;
         }
 
-        let input_str = app.search_input_text().to_string();
-        let t = ratatui::widgets::Paragraph::new(highlight_keywords_in_text(&log_text, input_str))
+        let search_matcher = app.search_matcher().ok();
+        let t = ratatui::widgets::Paragraph::new(highlight_keywords_in_text(
+            &log_text,
+            search_matcher.as_ref(),
+        ))
             .block(
                 Block::default()
                     .title(" [Log entry] ")
@@ -128,26 +372,48 @@ pub fn render(f: &mut Frame, app: &mut App) {
         return;
     }
 
-    let (tabs_area, preview_area, table_area) = (areas[1], areas[2], areas[3]);
+    let (tabs_area, preview_area, mut table_area) = (areas[1], areas[2], areas[3]);
+    if matches!(app.view_mode().back(), Some(ViewMode::FilterRules)) {
+        // The panel takes the top of the table area so that it's shown even when no entry is left
+        let panel_height =
+            (app.filter_rules().len().max(1) + 3).min(MAX_FILTER_RULES_PANEL_HEIGHT) as u16;
+        let panel_areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(panel_height), Constraint::Min(0)].as_ref())
+            .split(table_area);
+        render_filter_rules(f, app, panel_areas[0]);
+        table_area = panel_areas[1];
+    } else if matches!(app.view_mode().back(), Some(ViewMode::SearchResults)) {
+        let match_count = app.search_results().map_or(0, |results| results.matches.len());
+        let panel_height = ((match_count.max(1) + 2) as u16).min(table_area.height / 2);
+        let panel_areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(panel_height), Constraint::Min(0)].as_ref())
+            .split(table_area);
+        render_search_results(f, app, panel_areas[0]);
+        table_area = panel_areas[1];
+    }
+
+    // The level chips take the line right above the table
+    let table_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+        .split(table_area);
+    render_level_chips(f, app, table_areas[0]);
+    let table_area = table_areas[1];
     *app.table_view_state_mut().position_mut() = Some((table_area.left(), table_area.top()));
 
-    let text = if app.tabs()[app.selected_tab_index()]
-        .filtered_view_items
-        .data
-        .is_empty()
-    {
-        "".to_owned()
-    } else {
-        app.tabs()[app.selected_tab_index()]
-            .filtered_view_items
-            .data[app.tabs()[app.selected_tab_index()]
-            .filtered_view_items
-            .selected_item_index][LogEntryIndices::Log as usize]
-            .clone()
+    let items = &app.tabs()[app.selected_tab_index()].filtered_view_items;
+    let text = match items.data.get(items.selected_item_index) {
+        None => "".to_owned(),
+        Some(row) => match row.kind {
+            RowKind::Separator { hidden, .. } => separator_text(hidden),
+            _ => app.entry(row)[LogEntryIndices::Log as usize].clone(),
+        },
     };
 
-    let input_str = app.search_input_text().to_string();
-    let text = highlight_keywords_in_text(&text, input_str);
+    let search_matcher = app.search_matcher().ok();
+    let text = highlight_keywords_in_text(&text, search_matcher.as_ref());
 
     let preview = Paragraph::new(text)
         .wrap(Wrap { trim: true })
@@ -158,7 +424,38 @@ pub fn render(f: &mut Frame, app: &mut App) {
                 .title_alignment(ratatui::layout::Alignment::Center),
         )
         .bg(DEFAULT_BG_COLOR);
-    f.render_widget(preview, preview_area);
+
+    // The time prompts take the place of the preview while they're open
+    let time_prompt_title = match app.selected_input() {
+        Some(SelectedInput::TimeRange) => Some(
+            " [T]ime range: start..end, or ±30s / +30s / -30s around the selected entry (empty to clear) ",
+        ),
+        Some(SelectedInput::GoToTime) => {
+            Some(" [J]ump to time: a date, or +5m / -5m from the selected entry ")
+        }
+        _ => None,
+    };
+    if let Some(title) = time_prompt_title {
+        let mut title = vec![Span::raw(title)];
+        if let Some(error) = app.time_input_error() {
+            title.push(Span::styled(
+                format!(" {} ", error),
+                Style::default().fg(Color::White).bg(Color::Red),
+            ));
+        }
+
+        let prompt = Paragraph::new(app.time_input_text().to_string())
+            .block(Block::default().borders(Borders::ALL).title(Line::from(title)))
+            .bg(DEFAULT_HIGHLIGHT_COLOR);
+        f.render_widget(Clear, preview_area);
+        f.render_widget(prompt, preview_area);
+        f.set_cursor(
+            preview_area.x + (app.time_input_text().cursor() as u16) + 1,
+            preview_area.y + 1,
+        );
+    } else {
+        f.render_widget(preview, preview_area);
+    }
 
     let input_area = Layout::default()
         .direction(Direction::Horizontal)
@@ -167,12 +464,31 @@ pub fn render(f: &mut Frame, app: &mut App) {
 
     let (filter_area, search_area) = (input_area[0], input_area[1]);
 
+    // Unlinked inputs only apply to the selected tab
+    let (filter_title, search_title) = if app.inputs_linked() {
+        ("[F]ilter", "[S]earch")
+    } else {
+        ("[F]ilter this tab", "[S]earch this tab")
+    };
+    let search_title = match (app.search_match_position(), app.search_results()) {
+        (_, Some(results)) if !results.complete => format!(
+            "{} [{} matches, searching {:.0}%]",
+            search_title,
+            results.matches.len(),
+            results.checked as f64 * 100.0 / results.total.max(1) as f64
+        ),
+        (Some((Some(position), count)), _) => {
+            format!("{} [match {} of {}]", search_title, position, count)
+        }
+        (Some((None, count)), _) => format!("{} [{} matches]", search_title, count),
+        _ => search_title.to_owned(),
+    };
     let mut filter = Paragraph::new(app.filter_input_text().to_string())
-        .block(Block::default().borders(Borders::ALL).title("[F]ilter"))
+        .block(input_block(filter_title, app.filter_mode(), app.filter_query().err()))
         .bg(DEFAULT_BG_COLOR);
 
     let mut search = Paragraph::new(app.search_input_text().to_string())
-        .block(Block::default().borders(Borders::ALL).title("[S]earch"))
+        .block(input_block(&search_title, app.search_mode(), app.search_matcher().err()))
         .bg(DEFAULT_BG_COLOR);
 
     if let Some(SelectedInput::Filter) = &app.selected_input() {
@@ -192,12 +508,42 @@ pub fn render(f: &mut Frame, app: &mut App) {
     f.render_widget(filter, filter_area);
     f.render_widget(search, search_area);
 
+    let is_combined_tab = matches!(
+        app.tabs()[app.selected_tab_index()].tab_type,
+        TabType::Combined
+    );
+
+    // Extra columns of delimited (CSV/TSV) files are shown as table columns (with the width of their longest value)
+    let mut field_columns = Vec::<(String, usize)>::new();
+    if !is_combined_tab {
+        let items = &app.tabs()[app.selected_tab_index()].filtered_view_items.data;
+        for item in items[app.get_view_buffer_range()]
+            .iter()
+            .map(|row| app.entry(row))
+            .filter(|item| item.format == DELIMITED_FORMAT)
+        {
+            for (name, value) in &item.fields {
+                let width = value.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+                match field_columns.iter_mut().find(|(column_name, _)| column_name == name) {
+                    Some((_, column_width)) => *column_width = (*column_width).max(width),
+                    None => field_columns.push((name.clone(), width.max(name.chars().count()))),
+                }
+            }
+        }
+    }
+
     // Show the file name only in the combined tab
-    let column_names = if let TabType::Combined = app.tabs()[app.selected_tab_index()].tab_type {
-        ["source", "date", "level", "log"].to_vec()
+    let date_column_name = match app.display_zone() {
+        Some(zone) => format!("date ({})", zone),
+        None => "date".to_owned(),
+    };
+    let mut column_names = if is_combined_tab {
+        ["source", "session", date_column_name.as_str(), "level"].to_vec()
     } else {
-        ["date", "level", "log"].to_vec()
+        ["session", date_column_name.as_str(), "level"].to_vec()
     };
+    column_names.extend(field_columns.iter().map(|(name, _)| name.as_str()));
+    column_names.push("log");
 
     let header_cells = column_names
         .iter()
@@ -231,8 +577,22 @@ pub fn render(f: &mut Frame, app: &mut App) {
             return;
         }
 
-        let rows = items[app.get_view_buffer_range()].iter().map(|item| {
+        let rows = items[app.get_view_buffer_range()].iter().map(|row| {
+            // Separators only tell how many entries they hide
+            if let RowKind::Separator { hidden, .. } = row.kind {
+                let mut cells = vec![Cell::from(""); column_names.len() - 1];
+                cells.push(Cell::from(separator_text(hidden)));
+                return Row::new(cells).style(
+                    Style::default()
+                        .bg(DEFAULT_BG_COLOR)
+                        .fg(Color::Gray)
+                        .add_modifier(Modifier::ITALIC),
+                );
+            }
+
+            let item = app.entry(row);
             let height = item
+                .columns
                 .iter()
                 .map(|content| content.chars().filter(|c| *c == '\n').count())
                 .max()
@@ -240,46 +600,85 @@ pub fn render(f: &mut Frame, app: &mut App) {
                 + 1;
 
             // Show the file name column only in the combined tab
-            let starting_cell =
-                if let TabType::Combined = app.tabs()[app.selected_tab_index()].tab_type {
-                    LogEntryIndices::FileName as usize
-                } else {
-                    LogEntryIndices::Date as usize
-                };
-            let cells = item[starting_cell..item.len()].iter().map(|c: &String| {
-                let input_str = app.search_input_text().to_string();
-                Cell::from(highlight_keywords_in_text(c, input_str))
-            });
-            let row = Row::new(cells).height(height as u16);
-            let color = match item[LogEntryIndices::Level as usize].as_str() {
-                "ERROR" => (Color::Red, Color::White),
-                "WARN" => (Color::LightYellow, Color::Black),
-                _ => (DEFAULT_BG_COLOR, Color::White),
+            let starting_cell = if is_combined_tab {
+                LogEntryIndices::FileName as usize
+            } else {
+                LogEntryIndices::Session as usize
             };
-
-            row.style(Style::default().bg(color.0).fg(color.1))
+            let date = match (app.display_zone(), item.timestamp) {
+                (Some(zone), Some(timestamp)) => format_timestamp(zone.to_zone_time(timestamp)),
+                _ => item[LogEntryIndices::Date as usize].clone(),
+            };
+            let field_values = field_columns.iter().map(|(name, _)| {
+                item.fields
+                    .iter()
+                    .find(|(field_name, _)| field_name == name)
+                    .map_or("", |(_, value)| value.as_str())
+            });
+            let mut cells = item.columns[starting_cell..LogEntryIndices::Log as usize]
+                .iter()
+                .map(String::as_str)
+                .chain(field_values)
+                .enumerate()
+                .map(|(index, c): (usize, &str)| {
+                    if starting_cell + index == LogEntryIndices::Date as usize {
+                        return Cell::from(date.clone());
+                    }
+
+                    if starting_cell + index == LogEntryIndices::Level as usize
+                        && item.severity_inferred
+                    {
+                        // Inferred levels are marked so that they can't be mistaken for the ones written in the log
+                        return Cell::from(format!("~{:?}", item.severity.unwrap()))
+                            .add_modifier(Modifier::ITALIC);
+                    }
+
+                    Cell::from(highlight_keywords_in_text(c, search_matcher.as_ref()))
+                })
+                .collect::<Vec<Cell>>();
+            cells.push(Cell::from(highlight_keywords_in_text(
+                &item[LogEntryIndices::Log as usize],
+                search_matcher.as_ref(),
+            )));
+            let table_row = Row::new(cells).height(height as u16);
+            match row.kind {
+                // Context rows only help reading the matches, so they stand out less
+                RowKind::Context => table_row.style(
+                    Style::default()
+                        .bg(DEFAULT_BG_COLOR)
+                        .fg(Color::Gray)
+                        .add_modifier(Modifier::DIM),
+                ),
+                _ => table_row.style(severity_style(item.severity, item.severity_inferred)),
+            }
         });
 
         rows
     };
 
-    let column_widts = if let TabType::Combined = app.tabs()[app.selected_tab_index()].tab_type {
+    let mut column_widts = if is_combined_tab {
         [
             // Show the file name only in the combined tab
             Constraint::Length(13),
+            Constraint::Length(7),
             Constraint::Length(24),
             Constraint::Length(6),
-            Constraint::Percentage(100),
         ]
         .to_vec()
     } else {
         [
+            Constraint::Length(7),
             Constraint::Length(24),
             Constraint::Length(6),
-            Constraint::Percentage(100),
         ]
         .to_vec()
     };
+    column_widts.extend(
+        field_columns
+            .iter()
+            .map(|(_, width)| Constraint::Length((*width).min(MAX_FIELD_COLUMN_WIDTH) as u16)),
+    );
+    column_widts.push(Constraint::Percentage(100));
 
     let t = Table::new(rows, column_widts.clone())
         .header(header)
@@ -292,12 +691,54 @@ pub fn render(f: &mut Frame, app: &mut App) {
             Block::default()
                 .borders(Borders::BOTTOM)
                 .title(format!(
-                    " Page [{}] ",
+                    " Page [{}] {}{}{}{}{}{}{}{}",
                     app.tabs()[app.selected_tab_index()]
                         .filtered_view_items
                         .selected_item_index
                         / app.view_buffer_size()
-                        + 1
+                        + 1,
+                    if app.latest_session_only() {
+                        "[Latest session only] "
+                    } else {
+                        ""
+                    },
+                    app.selected_tab_zone()
+                        .map(|zone| format!("[Dates written in {}] ", zone))
+                        .unwrap_or_default(),
+                    match app.selected_clock_offset() {
+                        0 => "".to_owned(),
+                        clock_offset => format!(
+                            "[Clock offset {:+.3}s] ",
+                            clock_offset as f64 / 1_000_000_000.0
+                        ),
+                    },
+                    if app.clock_sync_mark().is_some() {
+                        "[Sync mark set] "
+                    } else {
+                        ""
+                    },
+                    app.time_range()
+                        .map(|(start, end)| {
+                            let zone = app.display_zone().unwrap_or(Zone::Local);
+                            let format_side = |timestamp: i64| match timestamp {
+                                i64::MIN | i64::MAX => "...".to_owned(),
+                                timestamp => format_timestamp(zone.to_zone_time(timestamp)),
+                            };
+                            format!("[Time {} .. {}] ", format_side(start), format_side(end))
+                        })
+                        .unwrap_or_default(),
+                    match app.context_lines() {
+                        0 => "".to_owned(),
+                        context_lines => format!("[Context ±{}] ", context_lines),
+                    },
+                    if app.sort_by_level() {
+                        "[Sorted by level] "
+                    } else {
+                        ""
+                    },
+                    app.filter_progress()
+                        .map(|progress| format!("[Filtering {:.0}%] ", progress * 100.0))
+                        .unwrap_or_default()
                 ))
                 .title_position(Position::Bottom),
         )
@@ -1,9 +1,8 @@
 use std::sync::{Arc, Mutex};
 
 use crate::{
-    app::SelectedInput,
+    app::{SelectedInput, LEVEL_FILTERS},
     event::EventHandler,
-    tab::TabType,
     thirdparty::input::{Input, InputRequest},
     App, ViewMode,
 };
@@ -37,7 +36,9 @@ pub fn update(events: &EventHandler, app: Arc<Mutex<App>>) -> Result<()> {
                 match mouse_button {
                     MouseButton::Left => {
                         *app.mouse_position_mut() = (mouse_event.column, mouse_event.row);
-                        app.handle_table_mouse_click();
+                        if !app.handle_level_chip_click() {
+                            app.handle_table_mouse_click();
+                        }
                     }
                     MouseButton::Right => {
                         if app.view_mode().len() > 1 {
@@ -51,6 +52,9 @@ pub fn update(events: &EventHandler, app: Arc<Mutex<App>>) -> Result<()> {
         Event::Resize(_, _) => {}
     }
 
+    // Background jobs wake the loop up with ticks when they have new results
+    app.lock().unwrap().update_jobs();
+
     Ok(())
 }
 
@@ -63,9 +67,18 @@ fn handle_key_press(key: KeyEvent, app: &mut App) {
     if let Some(SelectedInput::Filter) = app.selected_input() {
         handle_filtered_mode(key.code, key.modifiers, app);
         return;
+    } else if let Some(SelectedInput::TimeRange | SelectedInput::GoToTime) = app.selected_input() {
+        handle_time_input_mode(key.code, key.modifiers, app);
+        return;
     } else if let Some(ViewMode::SearchView) = app.view_mode().back() {
         handle_search_mode(key.code, key.modifiers, app);
         return;
+    } else if let Some(ViewMode::FilterRules) = app.view_mode().back() {
+        handle_filter_rules_mode(key.code, app);
+        return;
+    } else if let Some(ViewMode::SearchResults) = app.view_mode().back() {
+        handle_search_results_mode(key.code, app);
+        return;
     }
 
     handle_normal_mode(key.code, app, key.modifiers);
@@ -86,12 +99,17 @@ fn handle_filtered_mode(key_code: KeyCode, key_modifiers: KeyModifiers, app: &mu
                     }
                     return;
                 }
+
+                if c == 'r' && key_modifiers & KeyModifiers::CONTROL == KeyModifiers::CONTROL {
+                    app.cycle_filter_mode();
+                    return;
+                }
                 handle_common_input(app.filter_input_text_mut(), key_code, key_modifiers);
-                app.filter_by_current_input(app.filter_input_text().to_string().clone());
+                app.filter_by_current_input();
             }
             KeyCode::Backspace => {
                 handle_common_input(app.filter_input_text_mut(), key_code, key_modifiers);
-                app.filter_by_current_input(app.filter_input_text().to_string().clone());
+                app.filter_by_current_input();
             }
             KeyCode::Enter => app.switch_to_item_view(),
 
@@ -110,12 +128,23 @@ fn handle_filtered_mode(key_code: KeyCode, key_modifiers: KeyModifiers, app: &mu
             }
             _ => {
                 handle_common_input(app.filter_input_text_mut(), key_code, key_modifiers);
-                app.filter_by_current_input(app.filter_input_text().to_string().clone());
+                app.filter_by_current_input();
             }
         }
     }
 }
 
+fn handle_time_input_mode(key_code: KeyCode, key_modifiers: KeyModifiers, app: &mut App) {
+    match key_code {
+        KeyCode::Enter => app.apply_time_input(),
+        KeyCode::Esc => *app.selected_input_mut() = None,
+        KeyCode::Char('c') if key_modifiers & KeyModifiers::CONTROL == KeyModifiers::CONTROL => {
+            *app.selected_input_mut() = None;
+        }
+        _ => handle_common_input(app.time_input_text_mut(), key_code, key_modifiers),
+    }
+}
+
 fn handle_common_input(input_element: &mut Input, key_code: KeyCode, key_modifiers: KeyModifiers) {
     match key_code {
         KeyCode::Char(c) => {
@@ -171,6 +200,11 @@ fn handle_search_mode(key_code: KeyCode, key_modifiers: KeyModifiers, app: &mut
                     app.view_mode_mut().pop_back();
                     return;
                 }
+
+                if c == 'r' && key_modifiers & KeyModifiers::CONTROL == KeyModifiers::CONTROL {
+                    app.cycle_search_mode();
+                    return;
+                }
                 handle_common_input(app.search_input_text_mut(), key_code, key_modifiers);
             }
             // Arrow keys to select filtered items
@@ -197,6 +231,37 @@ fn handle_search_mode(key_code: KeyCode, key_modifiers: KeyModifiers, app: &mut
     }
 }
 
+fn handle_filter_rules_mode(key_code: KeyCode, app: &mut App) {
+    match key_code {
+        KeyCode::Down | KeyCode::Char('j') => app.select_next_filter_rule(),
+        KeyCode::Up | KeyCode::Char('k') => app.select_previous_filter_rule(),
+        KeyCode::Char('J') => app.move_selected_filter_rule(false),
+        KeyCode::Char('K') => app.move_selected_filter_rule(true),
+        KeyCode::Char(' ') => app.toggle_selected_filter_rule(),
+        KeyCode::Char('i') => app.flip_selected_filter_rule(),
+        KeyCode::Char('a') => app.add_filter_rule_from_input(),
+        KeyCode::Char('d') | KeyCode::Delete => app.remove_selected_filter_rule(),
+        KeyCode::Char('f') => *app.selected_input_mut() = Some(SelectedInput::Filter),
+        KeyCode::Char('F') | KeyCode::Char('b') | KeyCode::Esc => app.toggle_filter_rules(),
+        KeyCode::Char('q') => *app.running_mut() = false,
+        _ => {}
+    }
+}
+
+fn handle_search_results_mode(key_code: KeyCode, app: &mut App) {
+    match key_code {
+        KeyCode::Down | KeyCode::Char('j') => app.move_search_result_selection(1),
+        KeyCode::Up | KeyCode::Char('k') => app.move_search_result_selection(-1),
+        KeyCode::PageDown => app.move_search_result_selection(10),
+        KeyCode::PageUp => app.move_search_result_selection(-10),
+        KeyCode::Char('a') => app.toggle_search_results_all_tabs(),
+        KeyCode::Enter => app.jump_to_selected_search_result(),
+        KeyCode::Char('S') | KeyCode::Char('b') | KeyCode::Esc => app.toggle_search_results(),
+        KeyCode::Char('q') => *app.running_mut() = false,
+        _ => {}
+    }
+}
+
 fn handle_normal_mode(key_code: KeyCode, app: &mut App, key_modifiers: KeyModifiers) {
     match key_code {
         KeyCode::Char('q') | KeyCode::Char('Q') => {
@@ -213,21 +278,7 @@ fn handle_normal_mode(key_code: KeyCode, app: &mut App, key_modifiers: KeyModifi
             }
             *app.last_key_input_mut() = Some('g');
         }
-        KeyCode::Char('x') => {
-            if app.tabs().is_empty() {
-                return;
-            } else if let TabType::Combined = app.tabs()[app.selected_tab_index()].tab_type {
-                return;
-            }
-
-            let index_to_remove = app.selected_tab_index();
-            if app.selected_tab_index() == app.tabs().len() - 1 {
-                *app.selected_tab_index_mut() = app.selected_tab_index().saturating_sub(1);
-            }
-
-            app.tabs_mut().remove(index_to_remove);
-            app.reload_combined_tab();
-        }
+        KeyCode::Char('x') => app.close_selected_tab(),
         KeyCode::Char('b') | KeyCode::Esc => {
             if app.view_mode().len() > 1 {
                 app.view_mode_mut().pop_back();
@@ -237,6 +288,9 @@ fn handle_normal_mode(key_code: KeyCode, app: &mut App, key_modifiers: KeyModifi
         KeyCode::End => app.end(),
         KeyCode::Enter => app.switch_to_item_view(),
         KeyCode::Char('o') => app.load_files(),
+        KeyCode::Char('l') if key_modifiers & KeyModifiers::CONTROL == KeyModifiers::CONTROL => {
+            app.toggle_inputs_linked();
+        }
         KeyCode::Right | KeyCode::Char('l') => app.next_tab(),
         KeyCode::Left | KeyCode::Char('h') => app.prev_tab(),
         KeyCode::Down | KeyCode::Char('j') => app.next(None),
@@ -271,6 +325,36 @@ fn handle_normal_mode(key_code: KeyCode, app: &mut App, key_modifiers: KeyModifi
         KeyCode::Char('t') => {
             app.set_tail_enabled(!app.tail_enabled());
         }
+        KeyCode::Char('O') => app.toggle_sort_by_level(),
+        KeyCode::Char('i') => {
+            app.set_infer_levels(!app.infer_levels());
+        }
+        KeyCode::Char(']') => app.next_session(),
+        KeyCode::Char('[') => app.previous_session(),
+        KeyCode::Char('L') => {
+            app.set_latest_session_only(!app.latest_session_only());
+        }
+        KeyCode::Char(c @ '1'..='8') => {
+            app.toggle_level(LEVEL_FILTERS[c as usize - '1' as usize]);
+        }
+        KeyCode::Char('0') => app.show_all_levels(),
+        KeyCode::Char('E') => app.toggle_warnings_and_errors_only(),
+        KeyCode::Char('F') => app.toggle_filter_rules(),
+        KeyCode::Char('S') => app.toggle_search_results(),
+        KeyCode::Char(')') => app.change_context_lines(1),
+        KeyCode::Char('(') => app.change_context_lines(-1),
+        KeyCode::Char('T') => app.open_time_input(SelectedInput::TimeRange),
+        KeyCode::Char('J') => app.open_time_input(SelectedInput::GoToTime),
+        KeyCode::Char('H') => app.hide_entries_like_selected(),
+        KeyCode::Char('z') => app.cycle_display_zone(),
+        KeyCode::Char('Z') => app.cycle_selected_tab_zone(),
+        KeyCode::Char('+') => app.shift_selected_clock(1_000_000_000),
+        KeyCode::Char('-') => app.shift_selected_clock(-1_000_000_000),
+        KeyCode::Char('>') => app.shift_selected_clock(100_000_000),
+        KeyCode::Char('<') => app.shift_selected_clock(-100_000_000),
+        KeyCode::Char('=') => app.reset_selected_clock(),
+        KeyCode::Char('m') => app.mark_selected_entry(),
+        KeyCode::Char('M') => app.sync_selected_clock(),
         KeyCode::Char('c') => {
             let mut log_text = app.selected_log_entry_in_text();
 
@@ -6,6 +6,8 @@ use ratatui::prelude::Text;
 use ratatui::style::Stylize;
 use serde_json::Value;
 
+use crate::matcher::Matcher;
+
 #[derive(Copy, Clone)]
 enum TextStyle {
     None,
@@ -14,35 +16,14 @@ enum TextStyle {
     Separator,
 }
 
-fn highlight_search_matches<'a>(text: &'a str, keywords: &'a str) -> Vec<(String, TextStyle)> {
-    let keywords = keywords
-        .split(',')
-        .map(|keyword| keyword.to_owned())
-        .collect::<Vec<String>>();
-
-    let mut keyword_positions_in_text = vec![];
-
-    {
-        let mut last_keyword_end_absolute = 0;
-        for keyword in keywords {
-            if keyword.is_empty() {
-                continue;
-            }
+fn highlight_search_matches(text: &str, matcher: Option<&Matcher>) -> Vec<(String, TextStyle)> {
+    let keyword_positions_in_text = matcher.map_or(vec![], |matcher| matcher.find_ranges(text));
 
-            if let Some(keyword_begin_relative) = text[last_keyword_end_absolute..]
-                .to_lowercase()
-                .find(&keyword.to_lowercase())
-            {
-                let keyword_begin_absolute = last_keyword_end_absolute + keyword_begin_relative;
-                last_keyword_end_absolute = keyword_begin_absolute + keyword.len();
-                keyword_positions_in_text.push((keyword_begin_absolute, last_keyword_end_absolute));
-            }
-        }
-    }
     let mut text_spans = vec![];
     let mut prev_span_end = 0;
 
-    for (keyword_start, keyword_end) in keyword_positions_in_text {
+    for keyword_range in keyword_positions_in_text {
+        let (keyword_start, keyword_end) = (keyword_range.start, keyword_range.end);
         text_spans.push((
             text[prev_span_end..keyword_start].to_owned(),
             TextStyle::None,
@@ -97,8 +78,8 @@ where
     spans_ret
 }
 
-pub fn highlight_keywords_in_text(text: &str, keywords: String) -> Text<'_> {
-    let text_spans = highlight_search_matches(text, &keywords);
+pub fn highlight_keywords_in_text<'a>(text: &'a str, matcher: Option<&Matcher>) -> Text<'a> {
+    let text_spans = highlight_search_matches(text, matcher);
     let mut text_spans = highlight_chars(|chr| chr.is_numeric(), text_spans, TextStyle::Digit);
     text_spans = highlight_chars(
         |chr| {